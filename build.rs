@@ -0,0 +1,55 @@
+//! Generates a module that includes every solution in `src/bin` so the main binary can run them in-process.
+//! The module is only compiled with the `in_process` feature, so a day that does not compile yet only breaks `all --in-process`.
+use std::{env, fs, path::Path};
+
+fn is_number(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|c| c.is_ascii_digit())
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named `<year>-<day>.rs`, e.g. `2023-05.rs`.
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|name| {
+                    let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+                    (is_number(year, 4) && is_number(day, 2)).then(|| (year.into(), day.into()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort();
+
+    let mut module = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}-{day}.rs"));
+        module.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {:?}]\nmod day_{year}_{day};\n",
+            path.display().to_string()
+        ));
+    }
+
+    let entries: Vec<String> = puzzles
+        .iter()
+        .map(|(year, day)| format!("day_{year}_{day}::SOLUTION"))
+        .collect();
+
+    module.push_str(&format!(
+        "\n#[cfg(not(test))]\npub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[{}];\n",
+        entries.join(", ")
+    ));
+    module.push_str(
+        "\n#[cfg(test)]\npub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];\n",
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), module).unwrap();
+}
@@ -1,9 +1,15 @@
 use std::cmp::max;
 use std::collections::HashMap;
 
+use advent_of_code::parse::{header, list, parse_all, unsigned, IResult};
 use itertools::Itertools;
+use nom::{
+    character::complete::{alpha1, space1},
+    combinator::map,
+    sequence::{pair, separated_pair},
+};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 #[derive(Debug)]
 struct Game {
@@ -57,34 +63,27 @@ struct Reveal {
     color: String,
 }
 
-fn parse_line(line: &str) -> Game {
-    let mut line = line.strip_prefix("Game ").unwrap();
-    let colon_idx = line.find(':').unwrap();
-
-    let id: u32 = line[..colon_idx].parse().unwrap();
-
-    line = &line[colon_idx + 1..];
-
-    let set_definitions: Vec<_> = line.split(';').collect();
-
-    let mut sets = Vec::new();
-    for set_definition in set_definitions {
-        let reveal_definitions: Vec<_> = set_definition.split(',').map(|x| x.trim()).collect();
-
-        let mut cube_reveals = Vec::new();
-        for reveal_definition in reveal_definitions {
-            let space_idx = reveal_definition.find(' ').unwrap();
-
-            let count: u32 = reveal_definition[..space_idx].parse().unwrap();
-            let color = reveal_definition[space_idx + 1..].to_string();
+fn parse_reveal(input: &str) -> IResult<'_, Reveal> {
+    map(
+        separated_pair(unsigned, space1, alpha1),
+        |(count, color): (u32, &str)| Reveal {
+            count,
+            color: color.to_string(),
+        },
+    )(input)
+}
 
-            cube_reveals.push(Reveal { count, color })
-        }
+fn parse_game(input: &str) -> IResult<'_, Game> {
+    let set = map(list(",", parse_reveal), |cube_reveals| Set { cube_reveals });
 
-        sets.push(Set { cube_reveals });
-    }
+    map(pair(header("Game"), list(";", set)), |(id, sets)| Game {
+        id,
+        sets,
+    })(input)
+}
 
-    Game { id, sets }
+fn parse_line(line: &str) -> Game {
+    parse_all(parse_game, line).unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -134,13 +133,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2286));
     }
 }
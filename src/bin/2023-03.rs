@@ -0,0 +1,107 @@
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::grid::{Grid, Point};
+
+advent_of_code::solution!(2023, 3);
+
+fn is_symbol(ch: char) -> bool {
+    !ch.is_ascii_digit() && ch != '.'
+}
+
+fn is_gear(ch: char) -> bool {
+    ch == '*'
+}
+
+/// A number of the schematic with the positions of its digits.
+struct PartNumber {
+    value: u32,
+    digits: Vec<Point>,
+}
+
+impl PartNumber {
+    /// Positions around the number that match `predicate`.
+    fn adjacent(&self, schematic: &Grid<char>, predicate: fn(char) -> bool) -> HashSet<Point> {
+        self.digits
+            .iter()
+            .flat_map(|&digit| schematic.neighbors8(digit))
+            .filter(|&(_, &ch)| predicate(ch))
+            .map(|(point, _)| point)
+            .collect()
+    }
+}
+
+fn numbers(schematic: &Grid<char>) -> Vec<PartNumber> {
+    let mut numbers = vec![];
+    let mut current: Option<PartNumber> = None;
+
+    for (point, &ch) in schematic.iter() {
+        // Numbers end at the end of a row.
+        if point.x == 0 {
+            numbers.extend(current.take());
+        }
+
+        match ch.to_digit(10) {
+            Some(digit) => {
+                let number = current.get_or_insert(PartNumber {
+                    value: 0,
+                    digits: vec![],
+                });
+                number.value = number.value * 10 + digit;
+                number.digits.push(point);
+            }
+            None => numbers.extend(current.take()),
+        }
+    }
+
+    numbers.extend(current);
+    numbers
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let schematic: Grid<char> = input.parse().ok()?;
+
+    let result = numbers(&schematic)
+        .iter()
+        .filter(|number| !number.adjacent(&schematic, is_symbol).is_empty())
+        .map(|number| number.value)
+        .sum();
+
+    Some(result)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let schematic: Grid<char> = input.parse().ok()?;
+
+    let mut found_gears: HashMap<Point, Vec<u32>> = HashMap::new();
+
+    for number in numbers(&schematic) {
+        for gear in number.adjacent(&schematic, is_gear) {
+            found_gears.entry(gear).or_default().push(number.value);
+        }
+    }
+
+    let result = found_gears
+        .into_values()
+        .filter(|part_numbers| part_numbers.len() == 2)
+        .map(|part_numbers| part_numbers.iter().product::<u32>())
+        .sum();
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4361));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(467835));
+    }
+}
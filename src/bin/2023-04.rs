@@ -1,15 +1,10 @@
 use std::collections::BTreeMap;
 
+use advent_of_code::parse::{header, numbers, parse_all, ws, IResult};
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete::{char, digit1};
-use nom::combinator::{map, map_opt};
-use nom::multi::{many1, many_till};
-use nom::{
-    character::complete::multispace0, error::ParseError, sequence::delimited, Finish, IResult,
-};
+use nom::{character::complete::char, sequence::separated_pair};
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
 #[derive(Debug)]
 struct ScratchCard {
@@ -20,23 +15,18 @@ struct ScratchCard {
 
 impl ScratchCard {
     fn from_string(input: &str) -> ScratchCard {
-        let (_, sc) = ScratchCard::parse(input).finish().unwrap();
-        sc
+        parse_all(ScratchCard::parse, input).unwrap()
     }
 
-    fn parse(input: &str) -> IResult<&str, ScratchCard> {
-        let number = || map_opt(ws(digit1), |n| n.parse::<u8>().ok());
-
-        let (input, id) = delimited(tag("Card "), number(), char(':'))(input)?;
-
-        let (input, winning_numbers) =
-            map(many_till(number(), char('|')), |(numbers, _)| numbers)(input)?;
-        let (input, have_numbers) = many1(number())(input)?;
+    fn parse(input: &str) -> IResult<'_, ScratchCard> {
+        let (input, id) = header("Card")(input)?;
+        let (input, (winning_numbers, have_numbers)) =
+            separated_pair(numbers, ws(char('|')), numbers)(input)?;
 
         Ok((
             input,
             ScratchCard {
-                id: id as usize,
+                id,
                 winning_numbers,
                 have_numbers,
             },
@@ -61,15 +51,6 @@ impl ScratchCard {
     }
 }
 
-fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(
-    inner: F,
-) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
-where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
-{
-    delimited(multispace0, inner, multispace0)
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
     let result = input
         .lines()
@@ -117,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(30));
     }
 }
@@ -0,0 +1,243 @@
+use advent_of_code::parse::{blocks, lines, parse_all, IResult, ParseError};
+use advent_of_code::ranges::{Interval, IntervalSet, RangeMap};
+use itertools::Itertools;
+use nom::{bytes::complete::take_while1, combinator::recognize};
+use std::{error::Error, fmt, num::ParseIntError};
+advent_of_code::solution!(2023, 5, try_parse = Almanac::parse);
+
+#[derive(Debug, PartialEq)]
+pub enum AlmanacError {
+    Parse(ParseError),
+    MissingSeeds,
+    UnpairedSeed(u64),
+    InvalidNumber(String, ParseIntError),
+    InvalidMapping(String),
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::Parse(_) => write!(f, "invalid almanac"),
+            AlmanacError::MissingSeeds => write!(f, "almanac does not list any seeds"),
+            AlmanacError::UnpairedSeed(seed) => {
+                write!(f, "seed range starting at {seed} has no length")
+            }
+            AlmanacError::InvalidNumber(s, _) => write!(f, "invalid number \"{s}\""),
+            AlmanacError::InvalidMapping(s) => write!(f, "invalid mapping \"{s}\""),
+        }
+    }
+}
+
+impl Error for AlmanacError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AlmanacError::Parse(e) => Some(e),
+            AlmanacError::InvalidNumber(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for AlmanacError {
+    fn from(e: ParseError) -> Self {
+        AlmanacError::Parse(e)
+    }
+}
+
+fn parse_number(s: &str) -> Result<u64, AlmanacError> {
+    s.parse()
+        .map_err(|e| AlmanacError::InvalidNumber(s.to_string(), e))
+}
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    mappings: Vec<Mappings>,
+}
+
+/// A non-empty line.
+fn line(input: &str) -> IResult<'_, &str> {
+    take_while1(|c| c != '\n')(input)
+}
+
+impl Almanac {
+    fn parse(input: &str) -> Result<Self, AlmanacError> {
+        let blocks = parse_all(blocks(recognize(lines(line))), input)?;
+        let seeds = blocks[0]
+            .strip_prefix("seeds: ")
+            .ok_or(AlmanacError::MissingSeeds)?
+            .split(' ')
+            .map(parse_number)
+            .try_collect()?;
+
+        let mappings = blocks
+            .iter()
+            .dropping(1)
+            .map(|block| {
+                let (_, body) = block.split_once('\n').unwrap_or((block, ""));
+                Mappings::parse(body)
+            })
+            .try_collect()?;
+
+        Ok(Self { seeds, mappings })
+    }
+}
+
+struct Mappings {
+    ranges: RangeMap<u64>,
+}
+
+impl Mappings {
+    fn parse(string: &str) -> Result<Self, AlmanacError> {
+        let ranges = string.lines().map(parse_mapping).try_collect()?;
+        Ok(Self { ranges })
+    }
+
+    fn map(&self, n: u64) -> u64 {
+        self.ranges.map(n)
+    }
+
+    fn map_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.ranges.map_set(ranges)
+    }
+}
+
+/// Parses a line like `50 98 2` into the source interval and the destination of its start.
+fn parse_mapping(string: &str) -> Result<(Interval<u64>, u64), AlmanacError> {
+    let Some((destination_start, source_start, size)) = string.split(' ').collect_tuple() else {
+        return Err(AlmanacError::InvalidMapping(string.to_string()));
+    };
+
+    let source = Interval::from_len(parse_number(source_start)?, parse_number(size)?);
+    Ok((source, parse_number(destination_start)?))
+}
+
+pub fn part_one(almanac: &Almanac) -> Result<u64, AlmanacError> {
+    let Almanac { seeds, mappings } = almanac;
+
+    let min_location = seeds
+        .iter()
+        .map(|seed| {
+            let mut result = *seed;
+            for mapping in mappings.iter() {
+                result = mapping.map(result);
+            }
+            result
+        })
+        .min();
+
+    min_location.ok_or(AlmanacError::MissingSeeds)
+}
+
+pub fn part_two(almanac: &Almanac) -> Result<u64, AlmanacError> {
+    let Almanac { seeds, mappings } = almanac;
+
+    let seed_ranges: IntervalSet<u64> = seeds
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [start, len] => Ok(Interval::from_len(start, len)),
+            [start] => Err(AlmanacError::UnpairedSeed(start)),
+            _ => unreachable!(),
+        })
+        .try_collect()?;
+
+    let locations = mappings
+        .iter()
+        .fold(seed_ranges, |ranges, mapping| mapping.map_ranges(&ranges));
+
+    locations.min().ok_or(AlmanacError::MissingSeeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn mapping_map_in_range() {
+        let mapping = Mappings::parse("7 0 5").unwrap();
+
+        assert_eq!(mapping.map(3), 10)
+    }
+
+    #[test]
+    fn mapping_map_in_out_of_range() {
+        let mapping = Mappings::parse("7 0 5").unwrap();
+
+        assert_eq!(mapping.map(100), 100)
+    }
+
+    #[test]
+    fn mapping_map_bounds() {
+        let mapping = Mappings::parse("7 0 5").unwrap();
+
+        assert_eq!(mapping.map(0), 7);
+        assert_eq!(mapping.map(4), 11);
+        assert_eq!(mapping.map(5), 5);
+    }
+
+    #[test]
+    fn mappings_maps_correctly() {
+        let mappings = Mappings::parse(indoc! {"\
+        15 25 3
+        0 10 5
+        5 15 10
+        100 30 4
+        "})
+        .unwrap();
+
+        assert_eq!(mappings.map(0), 0);
+        assert_eq!(mappings.map(10), 0);
+        assert_eq!(mappings.map(14), 4);
+        assert_eq!(mappings.map(15), 5);
+        assert_eq!(mappings.map(29), 29);
+        assert_eq!(mappings.map(33), 103);
+    }
+
+    #[test]
+    fn mappings_map_ranges() {
+        let mappings = Mappings::parse("52 50 48\n50 98 2").unwrap();
+        let seeds = IntervalSet::from(Interval::from_len(79, 14));
+        let mapped = mappings.map_ranges(&seeds);
+
+        assert_eq!(mapped.intervals(), &[Interval::from_len(81, 14)]);
+
+        let seeds = IntervalSet::from(Interval::new(96, 100));
+        let mapped = mappings.map_ranges(&seeds);
+
+        assert_eq!(
+            mapped.intervals(),
+            &[Interval::new(50, 52), Interval::new(98, 100)]
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let almanac =
+            Almanac::parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap();
+        let result = part_one(&almanac);
+        assert_eq!(result, Ok(35));
+    }
+
+    #[test]
+    fn parse_reports_invalid_mapping() {
+        let almanac = Almanac::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98");
+        assert_eq!(
+            almanac.err(),
+            Some(AlmanacError::InvalidMapping("50 98".into()))
+        );
+    }
+
+    #[test]
+    fn parse_reports_malformed_blocks() {
+        let almanac = Almanac::parse("seeds: 79 14\n\n\nseed-to-soil map:\n50 98 2");
+        assert!(matches!(almanac.err(), Some(AlmanacError::Parse(_))));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let almanac =
+            Almanac::parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap();
+        let result = part_two(&almanac);
+        assert_eq!(result, Ok(46));
+    }
+}
@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -12,7 +14,8 @@ use std::str::FromStr;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
 pub struct Day(u8);
 
 impl Day {
@@ -55,6 +58,20 @@ impl PartialOrd<u8> for Day {
     }
 }
 
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.0
+    }
+}
+
+impl TryFrom<u8> for Day {
+    type Error = DayFromStrError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::new(day).ok_or(DayFromStrError)
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Day {
@@ -114,6 +131,131 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days of advent.
+///
+/// # Parsing
+/// A set can be parsed from a comma-separated list of days and ranges. Ranges follow
+/// Rust syntax, i.e. `3..7` excludes the end while `3..=7` includes it. Either bound may be omitted.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySet};
+/// let days: DaySet = "1,4..6,24..".parse().unwrap();
+/// let days: Vec<u8> = days.iter().map(Day::into_inner).collect();
+/// assert_eq!(days, vec![1, 4, 5, 24, 25]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates an empty [`DaySet`].
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a [`DaySet`] containing every day of advent.
+    #[must_use]
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    #[must_use]
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the days that are in `self`, `other` or both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the days that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Iterates the days of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::empty();
+
+        for selector in s.split(',').map(str::trim) {
+            let Some((start, end)) = selector.split_once("..") else {
+                set.insert(parse_day_bound(selector)?);
+                continue;
+            };
+
+            let start = match start {
+                "" => 1,
+                start => parse_day_bound(start)?.0,
+            };
+
+            let end = match end.strip_prefix('=') {
+                Some("") => return Err(DaySetFromStrError(selector.into())),
+                Some(end) => parse_day_bound(end)?.0,
+                None if end.is_empty() => 25,
+                None => parse_day_bound(end)?.0 - 1,
+            };
+
+            (start..=end).for_each(|day| set.insert(Day(day)));
+        }
+
+        Ok(set)
+    }
+}
+
+fn parse_day_bound(s: &str) -> Result<Day, DaySetFromStrError> {
+    s.parse().map_err(|_| DaySetFromStrError(s.into()))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selector `{}`, expecting days between 1 and 25 like `3`, `1,4,9`, `3..7` or `3..=7`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +276,39 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
+
+    fn days(set: DaySet) -> Vec<u8> {
+        set.iter().map(Day::into_inner).collect()
+    }
+
+    #[test]
+    fn day_set_parses_lists_and_ranges() {
+        assert_eq!(days("3".parse().unwrap()), vec![3]);
+        assert_eq!(days("1,4,9".parse().unwrap()), vec![1, 4, 9]);
+        assert_eq!(days("3..7".parse().unwrap()), vec![3, 4, 5, 6]);
+        assert_eq!(days("3..=7".parse().unwrap()), vec![3, 4, 5, 6, 7]);
+        assert_eq!(days("..3, 23..".parse().unwrap()), vec![1, 2, 23, 24, 25]);
+        assert_eq!("..".parse::<DaySet>().unwrap(), DaySet::all());
+        assert!(days("7..3".parse().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn day_set_rejects_invalid_selectors() {
+        assert!("0".parse::<DaySet>().is_err());
+        assert!("26".parse::<DaySet>().is_err());
+        assert!("1..26".parse::<DaySet>().is_err());
+        assert!("3..=".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("a..b".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn day_set_difference() {
+        let set: DaySet = "1..=5".parse().unwrap();
+        let skip: DaySet = "2,4".parse().unwrap();
+        assert_eq!(days(set.difference(&skip)), vec![1, 3, 5]);
+    }
 
     #[test]
     fn all_days_iterator() {
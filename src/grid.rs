@@ -0,0 +1,691 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Sub};
+use std::str::FromStr;
+
+/// A position on a [`Grid`]. `x` grows to the right, `y` grows downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The point one step away in `direction`.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The four points that share an edge with this point.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The eight points that share an edge or a corner with this point.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    #[must_use]
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, factor: isize) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A direction on a [`Grid`], listed clockwise starting with [`Direction::Up`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The directions that move along a row or a column.
+    pub const CARDINAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The directions that move along both a row and a column.
+    pub const DIAGONAL: [Self; 4] = [Self::UpRight, Self::DownRight, Self::DownLeft, Self::UpLeft];
+
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The change of position when moving one step in this direction.
+    #[must_use]
+    pub const fn offset(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::UpRight => Point::new(1, -1),
+            Self::Right => Point::new(1, 0),
+            Self::DownRight => Point::new(1, 1),
+            Self::Down => Point::new(0, 1),
+            Self::DownLeft => Point::new(-1, 1),
+            Self::Left => Point::new(-1, 0),
+            Self::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    /// Rotates by 90 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Rotates by 90 degrees counterclockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Rotates clockwise by `steps` times 45 degrees.
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// # Parsing
+/// A grid of characters can be parsed from text with one row per line, other cell types can be
+/// parsed with [`Grid::parse_with`]. Displaying a grid yields the rows without a trailing newline.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Point};
+/// let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+/// assert_eq!(grid[Point::new(1, 1)], '#');
+/// assert_eq!(grid.to_string(), "#..\n.#.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseGridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseGridError::Ragged {
+                    line: y + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a grid with one row per line, converting each character with `parse`.
+    pub fn parse_with<F>(input: &str, mut parse: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        parse(c).ok_or(ParseGridError::InvalidCell {
+                            line: y + 1,
+                            column: x + 1,
+                            found: c,
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        #[allow(clippy::cast_possible_wrap)]
+        Point::new((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    /// Iterates every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    /// Iterates every position, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point_of(i))
+    }
+
+    /// The position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.point_of(i))
+    }
+
+    /// The neighbors of a position that share an edge with it and lie within the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbors4().filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// The neighbors of a position that share an edge or a corner with it and lie within the grid.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbors8().filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Walks from `start`, excluding it, in `direction` until the edge of the grid.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut point = start;
+        std::iter::from_fn(move || {
+            point = point.step(direction);
+            Some((point, self.get(point)?))
+        })
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals from the top left to the bottom right, starting with the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        self.lines_starting_at(
+            Direction::DownRight,
+            self.edge(0..self.height, true)
+                .rev()
+                .chain(self.edge(1..self.width, false)),
+        )
+    }
+
+    /// The diagonals from the top right to the bottom left, starting with the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        #[allow(clippy::cast_possible_wrap)]
+        let right = self.width as isize - 1;
+        self.lines_starting_at(
+            Direction::DownLeft,
+            self.edge(0..self.width, false).chain(
+                self.edge(1..self.height, true)
+                    .map(move |p| Point::new(right, p.y)),
+            ),
+        )
+    }
+
+    /// Points on the top edge, or on the left edge if `vertical`.
+    fn edge(
+        &self,
+        range: std::ops::Range<usize>,
+        vertical: bool,
+    ) -> impl DoubleEndedIterator<Item = Point> {
+        range.map(move |i| {
+            #[allow(clippy::cast_possible_wrap)]
+            let i = i as isize;
+            if vertical {
+                Point::new(0, i)
+            } else {
+                Point::new(i, 0)
+            }
+        })
+    }
+
+    fn lines_starting_at(
+        &self,
+        direction: Direction,
+        starts: impl Iterator<Item = Point>,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        starts.map(move |start| {
+            self.get(start)
+                .into_iter()
+                .chain(self.ray(start, direction).map(|(_, cell)| cell))
+        })
+    }
+
+    /// Converts every cell.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along the diagonal from the top left to the bottom right, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .columns()
+                .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    #[must_use]
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Finds the region around `start`, i.e. every position that can be reached by steps to a neighbor
+    /// sharing an edge, where `connected(from, to)` holds. The region is empty if `start` is outside the grid.
+    pub fn flood_fill(&self, start: Point, connected: impl Fn(&T, &T) -> bool) -> Vec<Point> {
+        let mut visited = vec![false; self.cells.len()];
+        self.fill_region(start, &connected, &mut visited)
+    }
+
+    /// Partitions the grid into regions, see [`Grid::flood_fill`].
+    pub fn regions(&self, connected: impl Fn(&T, &T) -> bool) -> Vec<Vec<Point>> {
+        let mut visited = vec![false; self.cells.len()];
+        let mut regions = vec![];
+
+        for i in 0..self.cells.len() {
+            if !visited[i] {
+                regions.push(self.fill_region(self.point_of(i), &connected, &mut visited));
+            }
+        }
+
+        regions
+    }
+
+    fn fill_region(
+        &self,
+        start: Point,
+        connected: &impl Fn(&T, &T) -> bool,
+        visited: &mut [bool],
+    ) -> Vec<Point> {
+        let Some(index) = self.index_of(start) else {
+            return vec![];
+        };
+
+        let mut region = vec![];
+        let mut queue = VecDeque::from([start]);
+        visited[index] = true;
+
+        while let Some(point) = queue.pop_front() {
+            region.push(point);
+            let cell = &self[point];
+
+            for (next, next_cell) in self.neighbors4(point) {
+                let next_index = self.index_of(next).unwrap();
+                if !visited[next_index] && connected(cell, next_cell) {
+                    visited[next_index] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        region
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// Panics if the point lies outside the grid.
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {point} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    /// # Panics
+    /// Panics if the point lies outside the grid.
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point} is outside the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line does not have as many cells as the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expecting {expected} like the first line"
+            ),
+            ParseGridError::InvalidCell {
+                line,
+                column,
+                found,
+            } => write!(f, "invalid cell '{found}' at line {line}, column {column}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        INPUT.parse().unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), INPUT.trim_end());
+        assert_eq!(
+            "ab\nc\n".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidCell {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>().unwrap().to_string(), "");
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = grid();
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+
+        grid[Point::new(0, 0)] = 'x';
+        assert_eq!(grid.row(0), Some(&['x', 'b', 'c'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.position(|&c| c == 'e'), Some(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = grid();
+        let neighbors4: String = grid.neighbors4(Point::new(0, 0)).map(|(_, c)| c).collect();
+        let neighbors8: String = grid.neighbors8(Point::new(1, 0)).map(|(_, c)| c).collect();
+
+        assert_eq!(neighbors4, "bd");
+        assert_eq!(neighbors8, "cfeda");
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(collect(grid.columns()), ["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+
+        let ray: String = grid
+            .ray(Point::new(0, 0), Direction::Right)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "bc");
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn fills_regions() {
+        let grid: Grid<char> = "aab\nabb\nccb".parse().unwrap();
+
+        let mut region = grid.flood_fill(Point::new(2, 0), |a, b| a == b);
+        region.sort();
+        assert_eq!(
+            region,
+            [
+                Point::new(1, 1),
+                Point::new(2, 0),
+                Point::new(2, 1),
+                Point::new(2, 2)
+            ]
+        );
+        assert!(grid.flood_fill(Point::new(5, 5), |a, b| a == b).is_empty());
+        assert_eq!(grid.regions(|a, b| a == b).len(), 3);
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.opposite(), Direction::DownLeft);
+        assert_eq!(
+            Point::ORIGIN.step(Direction::DownLeft) * 2,
+            Point::new(-2, 2)
+        );
+        assert_eq!(Point::new(1, 2).manhattan_distance(Point::new(-1, 0)), 4);
+    }
+}
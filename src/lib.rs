@@ -1,4 +1,11 @@
 mod day;
+pub mod grid;
+pub mod parse;
+mod puzzle;
+pub mod ranges;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
@@ -1,55 +1,244 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, compare, download, examples, inputs, read, readme, scaffold, solve,
+};
+use advent_of_code::Year;
 use args::{parse, AppArguments};
+#[cfg(not(feature = "in_process"))]
+use std::{env, process, process::Command};
+
+/// Every solution in `src/bin`, included as modules by the build script.
+#[cfg(feature = "in_process")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
-    use std::process;
+    use std::{error::Error, path::PathBuf, process};
+
+    use advent_of_code::template::bench_history::parse_threshold;
+    use advent_of_code::template::commands::examples::Selection;
+    use advent_of_code::template::commands::{all, solve};
+    use advent_of_code::template::module_template::DEFAULT_RETURN_TYPE;
+    use advent_of_code::template::runner::{parse_duration, BenchConfig};
+    use advent_of_code::template::timings_export::{Export, Format};
+    use advent_of_code::{DaySet, PuzzleId, Year};
 
-    use advent_of_code::Day;
+    /// Slowdown of a part's median time, as a ratio, that `compare` reports as a regression.
+    const DEFAULT_THRESHOLD: f64 = 0.1;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        ReadmeStars {
+            year: Year,
+        },
+        ExtractExamples {
+            puzzle: PuzzleId,
+            selection: Selection,
+            list: bool,
+            answers: bool,
+            force: bool,
+        },
+        EncryptInputs {
+            year: Year,
+            days: DaySet,
+        },
+        DecryptInputs {
+            year: Year,
+            days: DaySet,
+            force: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+            template: Option<String>,
+            return_type: String,
+            force: bool,
+            split_examples: bool,
+            dry_run: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            puzzle: PuzzleId,
+            options: solve::Options,
         },
         All {
-            release: bool,
-            time: bool,
+            year: Year,
+            in_process: bool,
+            options: all::Options,
         },
+        Compare {
+            year: Year,
+            baseline: Option<String>,
+            threshold: f64,
+        },
+    }
+
+    fn parse_bench_config(args: &mut pico_args::Arguments) -> Result<BenchConfig, Box<dyn Error>> {
+        let defaults = BenchConfig::default();
+
+        let config = BenchConfig {
+            warmup: args
+                .opt_value_from_fn("--warmup", parse_duration)?
+                .unwrap_or(defaults.warmup),
+            budget: args
+                .opt_value_from_fn("--bench-time", parse_duration)?
+                .unwrap_or(defaults.budget),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(defaults.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(defaults.max_samples),
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads the `--year` option, defaulting to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn Error>> {
+        let year = args.opt_value_from_str("--year")?.or_else(Year::from_env);
+        Ok(year.ok_or("no year specified, pass `--year <year>` or set `AOC_YEAR`.")?)
+    }
+
+    /// Reads the year and the day, which is expected as the last free-standing argument.
+    fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<AppArguments, Box<dyn Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                year: parse_year(&mut args)?,
+                in_process: args.contains("--in-process"),
+                options: all::Options {
+                    is_release: args.contains("--release"),
+                    is_timed: args.contains("--time"),
+                    bench: parse_bench_config(&mut args)?,
+                    only_unsolved: args.contains("--only-unsolved"),
+                    jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    baseline: args.opt_value_from_str("--baseline")?,
+                    export: {
+                        let format: Option<Format> = args.opt_value_from_str("--format")?;
+                        let output: Option<PathBuf> = args.opt_value_from_str("--output")?;
+
+                        (format.is_some() || output.is_some()).then(|| Export {
+                            format: format.unwrap_or_default(),
+                            output,
+                        })
+                    },
+                    days: {
+                        let skipped = args
+                            .values_from_str::<_, DaySet>("--skip")?
+                            .into_iter()
+                            .fold(DaySet::empty(), |acc, set| acc.union(&set));
+
+                        args.opt_free_from_str::<DaySet>()?
+                            .unwrap_or_else(DaySet::all)
+                            .difference(&skipped)
+                    },
+                },
+            },
+            Some("compare") => AppArguments::Compare {
+                year: parse_year(&mut args)?,
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args
+                    .opt_value_from_fn("--threshold", parse_threshold)?
+                    .unwrap_or(DEFAULT_THRESHOLD),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => match args.subcommand()?.as_deref() {
+                Some("extract") => AppArguments::ExtractExamples {
+                    selection: Selection {
+                        shared: args.opt_value_from_str("--block")?,
+                        parts: [
+                            args.opt_value_from_str("--part-1")?,
+                            args.opt_value_from_str("--part-2")?,
+                        ],
+                    },
+                    list: args.contains("--list"),
+                    answers: args.contains("--answers"),
+                    force: args.contains("--force"),
+                    puzzle: parse_puzzle(&mut args)?,
+                },
+                Some(x) => {
+                    eprintln!("Unknown examples command: {x}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("No examples command specified, expecting `extract`.");
+                    process::exit(1);
+                }
+            },
+            Some("inputs") => match args.subcommand()?.as_deref() {
+                Some("encrypt") => AppArguments::EncryptInputs {
+                    year: parse_year(&mut args)?,
+                    days: args
+                        .opt_free_from_str::<DaySet>()?
+                        .unwrap_or_else(DaySet::all),
+                },
+                Some("decrypt") => AppArguments::DecryptInputs {
+                    year: parse_year(&mut args)?,
+                    force: args.contains("--force"),
+                    days: args
+                        .opt_free_from_str::<DaySet>()?
+                        .unwrap_or_else(DaySet::all),
+                },
+                Some(x) => {
+                    eprintln!("Unknown inputs command: {x}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("No inputs command specified, expecting `encrypt` or `decrypt`.");
+                    process::exit(1);
+                }
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("readme") => match args.subcommand()?.as_deref() {
+                Some("stars") => AppArguments::ReadmeStars {
+                    year: parse_year(&mut args)?,
+                },
+                Some(x) => {
+                    eprintln!("Unknown readme command: {x}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("No readme command specified, expecting `stars`.");
+                    process::exit(1);
+                }
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                template: args.opt_value_from_str("--template")?,
+                return_type: args
+                    .opt_value_from_str("--return-type")?
+                    .unwrap_or_else(|| DEFAULT_RETURN_TYPE.into()),
+                force: args.contains("--force"),
+                split_examples: args.contains("--split-examples"),
+                dry_run: args.contains("--dry-run"),
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                options: solve::Options {
+                    is_release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    is_timed: args.contains("--time"),
+                    bench: parse_bench_config(&mut args)?,
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    input: args.opt_value_from_str("--input")?,
+                    examples: args.contains("--examples"),
+                },
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -70,6 +259,41 @@ mod args {
     }
 }
 
+#[cfg(feature = "in_process")]
+fn handle_all(year: Year, in_process: bool, options: all::Options) {
+    all::handle(year, options, in_process.then_some(solutions::SOLUTIONS));
+}
+
+/// Without the `in_process` feature, the solutions are not part of this binary. `all --in-process` then runs again
+/// in a build with the feature, so a day that does not compile yet does not break the other commands.
+#[cfg(not(feature = "in_process"))]
+fn handle_all(year: Year, in_process: bool, options: all::Options) {
+    if !in_process {
+        all::handle(year, options, None);
+        return;
+    }
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--features",
+            "in_process",
+            "--",
+        ])
+        .args(env::args().skip(1))
+        .status();
+
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to build the solutions in-process: {e}");
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -77,16 +301,46 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            AppArguments::All {
+                year,
+                in_process,
+                options,
+            } => handle_all(year, in_process, options),
+            AppArguments::Compare {
+                year,
+                baseline,
+                threshold,
+            } => compare::handle(year, baseline.as_deref(), threshold),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::ExtractExamples {
+                puzzle,
+                selection,
+                list,
+                answers,
+                force,
+            } => examples::handle(puzzle, selection, list, answers, force),
+            AppArguments::EncryptInputs { year, days } => inputs::encrypt(year, &days),
+            AppArguments::DecryptInputs { year, days, force } => {
+                inputs::decrypt(year, &days, force);
+            }
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::ReadmeStars { year } => readme::stars(year),
+            AppArguments::Scaffold {
+                puzzle,
+                template,
+                return_type,
+                force,
+                split_examples,
+                dry_run,
+            } => scaffold::handle(
+                puzzle,
+                template.as_deref(),
+                &return_type,
+                force,
+                split_examples,
+                dry_run,
+            ),
+            AppArguments::Solve { puzzle, options } => solve::handle(puzzle, options),
         },
     };
 }
@@ -0,0 +1,225 @@
+//! Parser combinators for common puzzle input formats, built on [`nom`].
+//!
+//! ```
+//! # use advent_of_code::parse::{header, numbers, parse_all, ws, IResult};
+//! # use nom::{character::complete::char, sequence::{pair, separated_pair}};
+//! fn card(input: &str) -> IResult<'_, (u32, (Vec<u8>, Vec<u8>))> {
+//!     pair(header("Card"), separated_pair(numbers, ws(char('|')), numbers))(input)
+//! }
+//!
+//! let (id, (winning, have)) = parse_all(card, "Card 1: 41 48 | 83 86  6").unwrap();
+//! assert_eq!((id, winning, have), (1, vec![41, 48], vec![83, 86, 6]));
+//! ```
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::separated_list1,
+    sequence::{delimited, pair, terminated, tuple},
+    Finish, Parser,
+};
+
+use crate::grid::Grid;
+
+/// The result of the parsers in this module.
+pub type IResult<'a, O> = nom::IResult<&'a str, O>;
+
+/// Skips spaces and tabs around `inner`.
+pub fn ws<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> IResult<'a, O>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    delimited(space0, inner, space0)
+}
+
+/// Parses an unsigned integer of any width, e.g. `u8` or `u64`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<'_, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parses an integer with an optional sign of any width, e.g. `-12` as `i32`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<'_, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parses integers separated by spaces, e.g. `79 14 55 13`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<'_, Vec<T>> {
+    ws(separated_list1(space1, signed))(input)
+}
+
+/// Parses a header like `Card  12:` and returns its number.
+pub fn header<'a, T: FromStr>(label: &'a str) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    terminated(
+        delimited(pair(tag(label), space1), unsigned, pair(space0, char(':'))),
+        space0,
+    )
+}
+
+/// Parses one or more `item`s separated by `separator`, which may be surrounded by spaces.
+pub fn list<'a, O, F>(separator: &'a str, item: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(ws(tag(separator)), item)
+}
+
+/// Parses one `item` per line.
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(line_ending, item)
+}
+
+/// Parses blocks separated by a blank line, e.g. the maps of an almanac.
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(tuple((line_ending, line_ending)), block)
+}
+
+/// Parses a block of non-empty lines of the same length into a [`Grid`].
+pub fn grid(input: &str) -> IResult<'_, Grid<char>> {
+    map_res(
+        separated_list1(line_ending, take_while1(|c| c != '\n' && c != '\r')),
+        |rows: Vec<&str>| Grid::from_rows(rows.iter().map(|row| row.chars().collect()).collect()),
+    )(input)
+}
+
+/// Applies `parser` to the whole input, allowing trailing whitespace.
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::new(input, e.input, e.code))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error returned by [`parse_all`], pointing to where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    pub kind: ErrorKind,
+    /// The line that contains the error.
+    pub text: String,
+}
+
+impl ParseError {
+    /// Creates an error for the position of `remaining` in `input`.
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - remaining.len();
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        let text = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            kind,
+            text,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match &self.kind {
+            ErrorKind::Eof => "unexpected input",
+            ErrorKind::Digit | ErrorKind::MapRes => "expected a number",
+            ErrorKind::Tag | ErrorKind::Char => "unexpected character",
+            ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace",
+            kind => kind.description(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.message()
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {}^", " ".repeat(self.column - 1))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use nom::sequence::separated_pair;
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(unsigned::<u8>("42 rest"), Ok((" rest", 42)));
+        assert!(unsigned::<u8>("256").is_err());
+        assert_eq!(signed::<i64>("-12"), Ok(("", -12)));
+        assert_eq!(signed::<i64>("+7"), Ok(("", 7)));
+        assert_eq!(
+            numbers::<u64>(" 79 14  55 | 1"),
+            Ok(("| 1", vec![79, 14, 55]))
+        );
+    }
+
+    #[test]
+    fn parses_headers_and_lists() {
+        assert_eq!(header::<u32>("Game")("Game 12: 3 blue"), Ok(("3 blue", 12)));
+        assert_eq!(header::<u32>("Card")("Card   1:"), Ok(("", 1)));
+
+        let mut reveals = list(
+            ";",
+            list(",", separated_pair(unsigned::<u32>, space1, tag("red"))),
+        );
+        assert_eq!(
+            reveals("1 red, 2 red; 3 red"),
+            Ok(("", vec![vec![(1, "red"), (2, "red")], vec![(3, "red")]]))
+        );
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let input = "ab\ncd\n\nef\ngh\n";
+
+        let grids = parse_all(blocks(grid), input).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[1].to_string(), "ef\ngh");
+
+        let numbers = parse_all(blocks(lines(unsigned::<u32>)), "1\r\n2\r\n\r\n3").unwrap();
+        assert_eq!(numbers, [vec![1, 2], vec![3]]);
+
+        assert!(parse_all(grid, "ab\nc").is_err());
+    }
+
+    #[test]
+    fn reports_positions() {
+        let error = parse_all(lines(numbers::<u32>), "1 2\n3 x4\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: unexpected input\n  3 x4\n    ^"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the puzzle's solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, year, PuzzleId};
+/// let puzzle = PuzzleId::new(year!(2023), day!(5));
+/// assert_eq!(puzzle.to_string(), "2023-05")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of the puzzle's solution, relative to the project root. Uses forward slashes so it can be used in
+    /// markdown links as well.
+    ///
+    /// ```
+    /// # use advent_of_code::{day, year, PuzzleId};
+    /// assert_eq!(PuzzleId::new(year!(2023), day!(5)).bin_path(), "src/bin/2023-05.rs")
+    /// ```
+    #[must_use]
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
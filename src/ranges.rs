@@ -0,0 +1,480 @@
+use std::fmt::Display;
+use std::ops::{Add, Range, Sub};
+
+/// A half-open interval of values, i.e. `start` is included while `end` is not.
+///
+/// An interval with `end <= start` is empty.
+///
+/// ```
+/// # use advent_of_code::ranges::Interval;
+/// let interval = Interval::new(3, 7);
+/// assert!(interval.contains(3) && !interval.contains(7));
+/// assert_eq!(interval.intersection(&Interval::new(5, 10)), Some(Interval::new(5, 7)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval of `len` values beginning at `start`.
+    pub fn from_len(start: T, len: T) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self::new(start, start + len)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Number of values in the interval.
+    #[must_use]
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.end.max(self.start) - self.start
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The values in both intervals, [`None`] if there are none.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.start.max(other.start),
+            self.end.min(other.end),
+        ))
+        .filter(|i| !i.is_empty())
+    }
+
+    /// The values that are not in `other`, which can be split into two intervals if `other` lies inside `self`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> [Option<Self>; 2] {
+        if !self.overlaps(other) {
+            return [Some(*self).filter(|i| !i.is_empty()), None];
+        }
+
+        [
+            Some(Self::new(self.start, other.start)).filter(|i| !i.is_empty()),
+            Some(Self::new(other.end, self.end)).filter(|i| !i.is_empty()),
+        ]
+    }
+
+    /// Splits the interval into the values below `at` and the values from `at`, each [`None`] if there are none.
+    #[must_use]
+    pub fn split_at(&self, at: T) -> [Option<Self>; 2] {
+        let at = at.clamp(self.start, self.end.max(self.start));
+        [
+            Some(Self::new(self.start, at)).filter(|i| !i.is_empty()),
+            Some(Self::new(at, self.end)).filter(|i| !i.is_empty()),
+        ]
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of values, stored as sorted intervals that neither overlap nor touch.
+///
+/// ```
+/// # use advent_of_code::ranges::{Interval, IntervalSet};
+/// let set: IntervalSet<u32> = [Interval::new(0, 5), Interval::new(3, 8), Interval::new(10, 12)].into_iter().collect();
+/// assert_eq!(set.intervals(), &[Interval::new(0, 8), Interval::new(10, 12)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + std::iter::Sum,
+    {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    /// The smallest value of the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let index = self.intervals.partition_point(|i| i.end < interval.start);
+        let overlapping = self.intervals[index..]
+            .iter()
+            .take_while(|i| i.start <= interval.end)
+            .count();
+
+        let merged = self.intervals[index..index + overlapping]
+            .iter()
+            .fold(interval, |acc, i| {
+                Interval::new(acc.start.min(i.start), acc.end.max(i.end))
+            });
+
+        self.intervals
+            .splice(index..index + overlapping, std::iter::once(merged));
+    }
+
+    /// The values in `self`, `other` or both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for interval in &other.intervals {
+            union.insert(*interval);
+        }
+        union
+    }
+
+    /// The values in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = vec![];
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            intersection.extend(x.intersection(y));
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        // Intersections of disjoint, sorted intervals are disjoint and sorted as well.
+        Self {
+            intervals: intersection,
+        }
+    }
+
+    /// The values in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let intervals = other
+            .intervals
+            .iter()
+            .fold(self.intervals.clone(), |acc, o| {
+                acc.iter().flat_map(|i| i.difference(o)).flatten().collect()
+            });
+
+        Self { intervals }
+    }
+
+    /// Splits the intervals of the set at every value of `at`.
+    #[must_use]
+    pub fn split(&self, at: impl IntoIterator<Item = T>) -> Vec<Interval<T>> {
+        let mut at: Vec<T> = at.into_iter().collect();
+        at.sort_unstable();
+
+        self.intervals
+            .iter()
+            .flat_map(|interval| {
+                let mut pieces = vec![];
+                let mut rest = Some(*interval);
+
+                for &point in &at {
+                    let Some(current) = rest else { break };
+                    let [below, above] = current.split_at(point);
+                    pieces.extend(below);
+                    rest = above;
+                }
+
+                pieces.extend(rest);
+                pieces
+            })
+            .collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise-linear map that shifts every value of a source interval by the same offset.
+/// Values outside of every source interval map to themselves.
+///
+/// Source intervals are expected to not overlap, otherwise the rule with the lower start wins.
+///
+/// ```
+/// # use advent_of_code::ranges::{Interval, IntervalSet, RangeMap};
+/// // Maps 10..15 to 100..105.
+/// let map: RangeMap<u64> = [(Interval::new(10, 15), 100)].into_iter().collect();
+/// assert_eq!(map.map(12), 102);
+/// assert_eq!(
+///     map.map_set(&Interval::new(8, 12).into()).intervals(),
+///     &[Interval::new(8, 10), Interval::new(100, 102)],
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// Source intervals and the destination of their start, sorted by source.
+    rules: Vec<(Interval<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self { rules: vec![] }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule that maps `source.start` to `destination`, `source.start + 1` to `destination + 1` and so on.
+    pub fn insert(&mut self, source: Interval<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+        let index = self.rules.partition_point(|(s, _)| s < &source);
+        self.rules.insert(index, (source, destination));
+    }
+
+    #[must_use]
+    pub fn map(&self, value: T) -> T {
+        self.rules
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |&(source, destination)| {
+                destination + (value - source.start)
+            })
+    }
+
+    /// Maps every value of an interval.
+    #[must_use]
+    pub fn map_interval(&self, interval: Interval<T>) -> IntervalSet<T> {
+        let mut mapped = IntervalSet::new();
+        let mut cursor = interval.start;
+
+        for &(source, destination) in &self.rules {
+            if source.end <= cursor {
+                continue;
+            }
+            if source.start >= interval.end {
+                break;
+            }
+
+            if source.start > cursor {
+                mapped.insert(Interval::new(cursor, source.start));
+                cursor = source.start;
+            }
+
+            let end = source.end.min(interval.end);
+            let start = destination + (cursor - source.start);
+            mapped.insert(Interval::new(start, start + (end - cursor)));
+            cursor = end;
+        }
+
+        if cursor < interval.end {
+            mapped.insert(Interval::new(cursor, interval.end));
+        }
+
+        mapped
+    }
+
+    /// Maps every value of a set.
+    #[must_use]
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter().fold(IntervalSet::new(), |acc, &interval| {
+            acc.union(&self.map_interval(interval))
+        })
+    }
+}
+
+impl<T> FromIterator<(Interval<T>, T)> for RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalSet, RangeMap};
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        let interval = Interval::new(0, 10);
+
+        assert_eq!(interval.len(), 10);
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::from_len(3, 4), Interval::new(3, 7));
+        assert_eq!(interval.intersection(&Interval::new(10, 20)), None);
+        assert_eq!(
+            interval.difference(&Interval::new(3, 5)),
+            [Some(Interval::new(0, 3)), Some(Interval::new(5, 10))]
+        );
+        assert_eq!(
+            interval.difference(&Interval::new(-5, 5)),
+            [None, Some(Interval::new(5, 10))]
+        );
+        assert_eq!(
+            interval.difference(&Interval::new(20, 30)),
+            [Some(interval), None]
+        );
+        assert_eq!(interval.split_at(10), [Some(interval), None]);
+    }
+
+    #[test]
+    fn merges_intervals() {
+        let set = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (20, 20)]);
+
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.min(), Some(0));
+        assert!(set.contains(9) && !set.contains(3));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+        assert_eq!(
+            a.split([5, 25, 40]),
+            [
+                Interval::new(0, 5),
+                Interval::new(5, 10),
+                Interval::new(20, 25),
+                Interval::new(25, 30)
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let map: RangeMap<i64> = [(Interval::new(10, 20), 100), (Interval::new(30, 35), 0)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(map.map(5), 5);
+        assert_eq!(map.map(15), 105);
+        assert_eq!(map.map(34), 4);
+        assert_eq!(
+            map.map_interval(Interval::new(0, 40)),
+            set(&[(0, 10), (100, 110), (20, 30), (0, 5), (35, 40)])
+        );
+        assert_eq!(
+            map.map_set(&set(&[(12, 14), (31, 33)])),
+            set(&[(102, 104), (1, 3)])
+        );
+    }
+}
@@ -0,0 +1,126 @@
+/// Registry of accepted answers, stored as one TOML file per day in `data/<year>/answers`.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{get_data_path, project_root};
+use crate::PuzzleId;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers file: {e}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+/// The accepted answers of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Compares an answer against the stored one.
+    /// Returns [`None`] if no answer is stored for the part.
+    #[must_use]
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Option<bool> {
+        self.get(part).map(|expected| answer == Some(expected))
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    project_root().join(get_data_path(
+        "answers",
+        puzzle.year,
+        &format!("{}.toml", puzzle.day),
+    ))
+}
+
+/// Reads the stored answers for a day. A missing file yields empty [`Answers`].
+pub fn read(puzzle: PuzzleId) -> Result<Answers, Error> {
+    match fs::read_to_string(get_path(puzzle)) {
+        Ok(contents) => toml::from_str(&contents).map_err(|e| Error::Parser(e.to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn write(puzzle: PuzzleId, answers: &Answers) -> Result<(), Error> {
+    let contents = toml::to_string(answers).map_err(|e| Error::Parser(e.to_string()))?;
+    let path = get_path(puzzle);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Stores an accepted answer for one part of a day, keeping the other part untouched.
+pub fn store(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read(puzzle)?;
+    answers.set(part, answer);
+    write(puzzle, &answers)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn verifies_stored_answers() {
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        };
+
+        assert_eq!(answers.verify(1, Some("42")), Some(true));
+        assert_eq!(answers.verify(1, Some("41")), Some(false));
+        assert_eq!(answers.verify(1, None), Some(false));
+        assert_eq!(answers.verify(2, Some("42")), None);
+    }
+
+    #[test]
+    fn serializes_to_toml() {
+        let mut answers = Answers::default();
+        answers.set(2, "12345");
+
+        let serialized = toml::to_string(&answers).unwrap();
+        assert_eq!(serialized, "part_2 = \"12345\"\n");
+        assert_eq!(toml::from_str::<Answers>(&serialized).unwrap(), answers);
+    }
+}
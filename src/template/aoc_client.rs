@@ -0,0 +1,562 @@
+/// Client for the Advent of Code website: downloads inputs and puzzle descriptions and submits answers.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{get_data_path, project_root};
+use crate::PuzzleId;
+
+/// Environment variable holding the session cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable pointing to a file that holds the session cookie.
+/// Defaults to `~/.adventofcode.session`, the location used by aoc-cli.
+pub const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+
+/// Environment variable overriding the website's base URL, e.g. to point the client at a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding the user agent of requests. The website asks automated tools to identify
+/// themselves, e.g. with a link to the repository and a contact address.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), " v", env!("CARGO_PKG_VERSION"));
+
+/// The user agent from [`USER_AGENT_ENV`], falling back to [`DEFAULT_USER_AGENT`].
+fn user_agent() -> String {
+    env::var(USER_AGENT_ENV)
+        .ok()
+        .filter(|agent| !agent.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.into())
+}
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    Status { url: String, status: u16 },
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found, set `{SESSION_ENV}` or store it in `{}`.",
+                session_file_path().map_or_else(
+                    || "~/.adventofcode.session".into(),
+                    |path| path.display().to_string()
+                )
+            ),
+            Error::Status { url, status } => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+            Error::IO(e) => write!(f, "could not read session file: {e}"),
+        }
+    }
+}
+
+/// Response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer is wrong. `wait` is the time until the next answer can be submitted.
+    Wrong {
+        wait: Option<Duration>,
+    },
+    TooHigh {
+        wait: Option<Duration>,
+    },
+    TooLow {
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently.
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+    /// A response that could not be recognized, with the text of the response page.
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format_wait = |wait: &Option<Duration>| {
+            wait.map_or_else(String::new, |wait| {
+                format!(" Wait {} before trying again.", format_duration(wait))
+            })
+        };
+
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Wrong { wait } => {
+                write!(f, "That's not the right answer.{}", format_wait(wait))
+            }
+            Verdict::TooHigh { wait } => {
+                write!(f, "Your answer is too high.{}", format_wait(wait))
+            }
+            Verdict::TooLow { wait } => {
+                write!(f, "Your answer is too low.{}", format_wait(wait))
+            }
+            Verdict::RateLimited { wait } => write!(
+                f,
+                "You gave an answer too recently. Wait {} before trying again.",
+                format_duration(*wait)
+            ),
+            Verdict::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::Unknown(text) => write!(f, "Unexpected response: {text}"),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client with the session cookie from [`SESSION_ENV`] or the session file, and the base URL from
+    /// [`BASE_URL_ENV`], falling back to [`DEFAULT_BASE_URL`].
+    pub fn from_env() -> Result<Self, Error> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &read_session()?))
+    }
+
+    /// Downloads the personal puzzle input.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        self.get(&format!("{}/input", day_path(puzzle)))
+    }
+
+    /// Downloads the HTML page of the puzzle description.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        self.get(&day_path(puzzle))
+    }
+
+    /// Submits an answer for one part of a puzzle.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let url = format!("{}{}/answer", self.base_url, day_path(puzzle));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        Ok(parse_verdict(&read_response(&url, response)?))
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        read_response(&url, response)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+#[must_use]
+pub fn get_input_path(puzzle: PuzzleId) -> PathBuf {
+    project_root().join(get_data_path(
+        "inputs",
+        puzzle.year,
+        &format!("{}.txt", puzzle.day),
+    ))
+}
+
+#[must_use]
+pub fn get_puzzle_path(puzzle: PuzzleId) -> PathBuf {
+    project_root().join(get_data_path(
+        "puzzles",
+        puzzle.year,
+        &format!("{}.html", puzzle.day),
+    ))
+}
+
+/// Public URL of the puzzle description, e.g. `https://adventofcode.com/2023/day/5`.
+#[must_use]
+pub fn get_puzzle_url(puzzle: PuzzleId) -> String {
+    format!("{DEFAULT_BASE_URL}{}", day_path(puzzle))
+}
+
+fn day_path(puzzle: PuzzleId) -> String {
+    format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, Error> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| Error::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, _)) => Err(Error::Status {
+            url: url.into(),
+            status,
+        }),
+        Err(e) => Err(Error::Transport(e.to_string())),
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    env::var_os(SESSION_FILE_ENV)
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        })
+}
+
+/// Reads the session cookie from [`SESSION_ENV`], falling back to the session file.
+pub fn read_session() -> Result<String, Error> {
+    if let Some(session) = env::var(SESSION_ENV).ok().filter(|s| !s.trim().is_empty()) {
+        return Ok(session);
+    }
+
+    let path = session_file_path().ok_or(Error::MissingSession)?;
+
+    match fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session),
+        Ok(_) => Err(Error::MissingSession),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::MissingSession),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Parses the response page of a submitted answer.
+#[must_use]
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        match between(&text, "You have ", " left to wait").and_then(parse_wait) {
+            Some(wait) => Verdict::RateLimited { wait },
+            None => Verdict::Unknown(text),
+        }
+    } else if text.contains("That's not the right answer") {
+        let wait = between(&text, "lease wait ", " before trying again").and_then(parse_wait);
+
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh { wait }
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow { wait }
+        } else {
+            Verdict::Wrong { wait }
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &s[s.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+/// Parses the wait times used on response pages, e.g. `37s`, `1m 5s` or `one minute`.
+fn parse_wait(s: &str) -> Option<Duration> {
+    let mut tokens = s.split_whitespace();
+    let mut seconds = 0;
+
+    while let Some(token) = tokens.next() {
+        let (value, unit) = match token.find(|c: char| !c.is_ascii_digit()) {
+            Some(0) => (parse_number_word(token)?, tokens.next()?),
+            Some(i) => (token[..i].parse().ok()?, &token[i..]),
+            None => (token.parse().ok()?, tokens.next()?),
+        };
+
+        let factor = match unit.trim_end_matches(['.', ',']) {
+            "h" | "hour" | "hours" => 3600,
+            "m" | "minute" | "minutes" => 60,
+            "s" | "second" | "seconds" => 1,
+            _ => return None,
+        };
+
+        seconds += value * factor;
+    }
+
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+fn parse_number_word(word: &str) -> Option<u64> {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    let index = words.iter().position(|w| w.eq_ignore_ascii_case(word))?;
+    u64::try_from(index + 1).ok()
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match (seconds / 60, seconds % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+/// Converts the `<article>` elements of a page into plain text. Falls back to the whole page if there are none.
+#[must_use]
+pub fn article_text(html: &str) -> String {
+    let articles: Vec<&str> = html
+        .match_indices("<article")
+        .filter_map(|(start, _)| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .collect();
+
+    let text = if articles.is_empty() {
+        html_to_text(html)
+    } else {
+        articles
+            .iter()
+            .map(|article| html_to_text(article))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    collapse_blank_lines(&text)
+}
+
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .find(|s| !s.is_empty())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (tag.starts_with('/'), name.as_str()) {
+            (false, "li") => text.push_str("- "),
+            (false, "br") | (true, "li") => text.push('\n'),
+            (true, "p" | "h2" | "pre" | "ul") => text.push_str("\n\n"),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    text.push_str(&decode_entities(rest));
+    text
+}
+
+/// Title of a puzzle page, e.g. `Trebuchet?!` for `<h2>--- Day 1: Trebuchet?! ---</h2>`.
+#[must_use]
+pub fn puzzle_title(html: &str) -> Option<String> {
+    let heading = between(html, "<h2>", "</h2>")?;
+    let title = heading.trim().trim_matches('-').trim();
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+    Some(decode_entities(title))
+}
+
+/// Decodes the HTML entities that appear in puzzle descriptions.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn collapse_blank_lines(s: &str) -> String {
+    let mut lines: Vec<&str> = vec![];
+
+    for line in s.trim().lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_some_and(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use super::{article_text, parse_verdict, parse_wait, puzzle_title, Client, Error, Verdict};
+    use crate::{day, year, PuzzleId};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(5));
+
+    /// Serves a single request with the given response and returns the received request.
+    fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_bytes = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = serve_once(200, "1 2 3\n");
+        let client = Client::new(&base_url, "abc\n");
+
+        assert_eq!(client.input(PUZZLE).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input HTTP/1.1"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve_once(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = Client::new(&base_url, "abc");
+
+        assert_eq!(client.submit(PUZZLE, 2, "42").unwrap(), Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve_once(404, "not found");
+        let client = Client::new(&base_url, "abc");
+
+        let result = client.puzzle(PUZZLE);
+        assert!(matches!(result, Err(Error::Status { status: 404, .. })));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let page = |message: &str| {
+            format!("<html><main><article><p>{message}</p></article></main></html>")
+        };
+
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a>")),
+            Verdict::TooHigh { wait: Some(Duration::from_secs(60)) }
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer; your answer is too low.  Because you have guessed incorrectly 5 times on this puzzle, please wait 5 minutes before trying again.")),
+            Verdict::TooLow { wait: Some(Duration::from_secs(300)) }
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data.")),
+            Verdict::Wrong { wait: None }
+        );
+        assert_eq!(
+            parse_verdict(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a>")),
+            Verdict::RateLimited { wait: Duration::from_secs(65) }
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+        assert!(matches!(parse_verdict("<p>Hello</p>"), Verdict::Unknown(text) if text == "Hello"));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait("37s"), Some(Duration::from_secs(37)));
+        assert_eq!(parse_wait("2m 5s"), Some(Duration::from_secs(125)));
+        assert_eq!(parse_wait("one minute"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_wait("10 minutes"), Some(Duration::from_secs(600)));
+        assert_eq!(parse_wait("a while"), None);
+    }
+
+    #[test]
+    fn converts_articles_to_text() {
+        let html = "<body><header>nav</header><main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> &amp; broken.</p><pre><code>1abc2\npqr3stu8vwx\n</code></pre><ul><li>one</li><li>two</li></ul></article></main></body>";
+
+        assert_eq!(
+            article_text(html),
+            "--- Day 1: Trebuchet?! ---\n\nSomething is wrong & broken.\n\n1abc2\npqr3stu8vwx\n\n- one\n- two"
+        );
+        assert_eq!(puzzle_title(html), Some("Trebuchet?!".into()));
+        assert_eq!(puzzle_title("<main></main>"), None);
+    }
+}
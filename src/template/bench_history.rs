@@ -0,0 +1,277 @@
+/// History of benchmark runs, stored as one JSON line per run in `data/<year>/benchmarks/history.jsonl`.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{
+    get_data_path, project_root, readme_benchmarks::Timings, runner::Stats, runner::PARSE_PART,
+};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// Measurements of a single part in a [`Run`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub stats: Stats,
+}
+
+/// A benchmark run of all selected days of a year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Abbreviated hash of the commit the run was made on, [`None`] outside of a git repository.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Name of the baseline this run was saved as.
+    #[serde(default)]
+    pub baseline: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+impl Run {
+    /// Creates a run from the timings of the current checkout.
+    #[must_use]
+    pub fn new(timings: &[Timings], baseline: Option<String>) -> Self {
+        let entries = timings
+            .iter()
+            .flat_map(|timing| {
+                [
+                    (PARSE_PART, timing.parse),
+                    (1, timing.part_1),
+                    (2, timing.part_2),
+                ]
+                .into_iter()
+                .filter_map(|(part, stats)| {
+                    Some(Entry {
+                        day: timing.puzzle.day,
+                        part,
+                        stats: stats?,
+                    })
+                })
+            })
+            .collect();
+
+        Self {
+            commit: current_commit(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            baseline,
+            entries,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&Stats> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part)
+            .map(|entry| &entry.stats)
+    }
+}
+
+/// Difference in median time of a part between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: u8,
+    pub before: Stats,
+    pub after: Stats,
+}
+
+impl Change {
+    /// Relative change of the median, e.g. `0.1` if the part got 10% slower.
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let (before, after) = (
+            self.before.median_nanos as f64,
+            self.after.median_nanos as f64,
+        );
+        (after - before) / before.max(1.0)
+    }
+
+    /// Whether the part got slower by more than `threshold`, given as a ratio.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.ratio() > threshold
+    }
+}
+
+/// Compares the parts measured in both runs.
+#[must_use]
+pub fn compare(baseline: &Run, current: &Run) -> Vec<Change> {
+    current
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            Some(Change {
+                day: entry.day,
+                part: entry.part,
+                before: *baseline.get(entry.day, entry.part)?,
+                after: entry.stats,
+            })
+        })
+        .collect()
+}
+
+/// Parses a regression threshold given in percent, e.g. `10%` or `2.5`, into a ratio.
+pub fn parse_threshold(s: &str) -> Result<f64, String> {
+    let percent: f64 = s
+        .trim()
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| format!("invalid threshold: {s}"))?;
+
+    if percent < 0.0 {
+        return Err(format!(
+            "invalid threshold: {s}, expecting a positive percentage"
+        ));
+    }
+
+    Ok(percent / 100.0)
+}
+
+#[must_use]
+pub fn get_path(year: Year) -> PathBuf {
+    project_root().join(get_data_path("benchmarks", year, "history.jsonl"))
+}
+
+/// Reads all runs of a year, oldest first. A missing file yields an empty history.
+pub fn read(year: Year) -> Result<Vec<Run>, Error> {
+    match fs::read_to_string(get_path(year)) {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse(contents: &str) -> Result<Vec<Run>, Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|e| Error::Parser(e.to_string())))
+        .collect()
+}
+
+/// Appends a run to the history of a year.
+pub fn append(year: Year, run: &Run) -> Result<(), Error> {
+    let line = serde_json::to_string(run).map_err(|e| Error::Parser(e.to_string()))?;
+    let path = get_path(year);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, parse, parse_threshold, Entry, Run};
+    use crate::template::runner::Stats;
+    use crate::{day, Day};
+
+    fn entry(day: Day, part: u8, median_nanos: u64) -> Entry {
+        Entry {
+            day,
+            part,
+            stats: Stats {
+                median_nanos,
+                samples: 10,
+                ..Stats::default()
+            },
+        }
+    }
+
+    fn run(entries: Vec<Entry>) -> Run {
+        Run {
+            commit: Some("abc1234".into()),
+            timestamp: 1_700_000_000,
+            baseline: None,
+            entries,
+        }
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let runs = vec![
+            run(vec![entry(day!(1), 1, 100)]),
+            run(vec![entry(day!(1), 2, 200)]),
+        ];
+
+        let contents = runs
+            .iter()
+            .map(|run| serde_json::to_string(run).unwrap() + "\n")
+            .collect::<String>();
+
+        assert_eq!(parse(&contents).unwrap(), runs);
+        assert!(parse("not json").is_err());
+    }
+
+    #[test]
+    fn compares_common_parts() {
+        let baseline = run(vec![entry(day!(1), 1, 100), entry(day!(1), 2, 100)]);
+        let current = run(vec![
+            entry(day!(1), 1, 150),
+            entry(day!(1), 2, 95),
+            entry(day!(2), 1, 100),
+        ]);
+
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 2);
+        assert!((changes[0].ratio() - 0.5).abs() < 1e-9);
+        assert!(changes[0].is_regression(0.1));
+        assert!(!changes[1].is_regression(0.1));
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!(parse_threshold("10%"), Ok(0.1));
+        assert_eq!(parse_threshold("5"), Ok(0.05));
+        assert!(parse_threshold("-1%").is_err());
+        assert!(parse_threshold("fast").is_err());
+    }
+}
@@ -1,39 +1,196 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::{self, Write},
+    process::{self, ExitStatus},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::template::{
+    answers,
+    bench_history::{self, Run},
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    runner::{BenchConfig, PartRecord, RunConfig, Solution},
+    timings_export::{self, Export},
+    try_read_file, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
-
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+use crate::{DaySet, PuzzleId, Year};
+
+/// Controls which days the `all` command runs and how.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub bench: BenchConfig,
+    pub days: DaySet,
+    /// Skip days that have stored answers for both parts.
+    pub only_unsolved: bool,
+    /// Number of days run concurrently.
+    pub jobs: usize,
+    /// Limit for a single execution of a solution function.
+    pub timeout: Option<Duration>,
+    /// Name of the run in the benchmark history.
+    pub baseline: Option<String>,
+    /// Exports timings instead of updating the readme.
+    pub export: Option<Export>,
+}
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+/// Runs the solutions of the selected days of a year. If a registry of solutions is passed, days are run in-process instead of spawning `cargo run` for each day.
+/// With more than one job, days are run concurrently while their output is still printed in order.
+pub fn handle(year: Year, options: Options, registry: Option<&[Solution]>) {
+    let Options {
+        is_release,
+        is_timed,
+        bench,
+        days,
+        only_unsolved,
+        jobs,
+        timeout,
+        baseline,
+        export,
+    } = options;
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut timings: Vec<Timings> = vec![];
+    let mut mismatches: Vec<String> = vec![];
+
+    let selected_puzzles: Vec<PuzzleId> = days
+        .iter()
+        .map(|day| PuzzleId::new(year, day))
+        .filter(|puzzle| !only_unsolved || !is_solved(*puzzle))
+        .collect();
+
+    let jobs = if jobs > 1 && is_timed {
+        println!("{ANSI_ITALIC}Timing solutions, running days serially.{ANSI_RESET}\n");
+        1
+    } else if jobs > 1 && registry.is_some() {
+        println!("{ANSI_ITALIC}Running in-process, running days serially.{ANSI_RESET}\n");
+        1
+    } else {
+        jobs
+    };
 
-        if output.is_empty() {
+    let mut report = |puzzle: PuzzleId, records: Vec<PartRecord>| {
+        if records.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            mismatches.extend(
+                records
+                    .iter()
+                    .filter(|record| record.correct == Some(false))
+                    .map(|record| format!("Day {} Part {}", puzzle.day, record.part)),
+            );
+
+            let val = child_commands::collect_timings(&records, puzzle);
             timings.push(val);
         }
-    });
+    };
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    if jobs > 1 {
+        // build once upfront so concurrent `cargo run` invocations don't contend for the build lock.
+        if let Err(e) = child_commands::build_solutions(is_release) {
+            eprintln!("Failed to build solutions: {e}");
+            process::exit(1);
+        }
 
+        run_parallel(
+            &selected_puzzles,
+            jobs,
+            |puzzle| {
+                child_commands::run_solution(puzzle, is_timed, is_release, &bench, timeout, true)
+            },
+            |i, puzzle, run| {
+                print_header(i, puzzle);
+                let records = match run {
+                    Ok(run) => {
+                        let _ = io::stdout().write_all(&run.stdout);
+                        let _ = io::stderr().write_all(&run.stderr);
+                        run.records
+                    }
+                    Err(e) => {
+                        eprintln!("Could not run solution: {e}");
+                        vec![]
+                    }
+                };
+                report(puzzle, records);
+            },
+        );
+    } else {
+        selected_puzzles
+            .iter()
+            .enumerate()
+            .for_each(|(i, &puzzle)| {
+                print_header(i, puzzle);
+
+                let records = match registry {
+                    Some(registry) => {
+                        let config = RunConfig {
+                            timeout,
+                            bench: is_timed.then_some(bench),
+                            ..RunConfig::default()
+                        };
+                        run_in_process(puzzle, registry, &config)
+                    }
+                    None => match child_commands::run_solution(
+                        puzzle, is_timed, is_release, &bench, timeout, false,
+                    ) {
+                        Ok(run) => run.records,
+                        Err(e) => {
+                            eprintln!("Could not run solution: {e}");
+                            vec![]
+                        }
+                    },
+                };
+
+                report(puzzle, records);
+            });
+    }
+
+    if !mismatches.is_empty() {
+        eprintln!(
+            "\n{ANSI_BOLD}✘ Answers do not match stored answers:{ANSI_RESET} {}",
+            mismatches.join(", ")
+        );
+        process::exit(1);
+    }
+
+    let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+
+    if is_timed {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    }
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+    if let Some(export) = &export {
+        let output = export.output.as_deref();
+        match timings_export::write(export.format, output, year, &timings, total_millis) {
+            Ok(()) => {
+                if let Some(path) = output {
+                    println!("Successfully wrote timings to \"{}\".", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to export timings: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if is_timed && is_release {
+        match bench_history::append(year, &Run::new(&timings, baseline)) {
+            Ok(()) => println!(
+                "Appended run to benchmark history \"{}\".",
+                bench_history::get_path(year).display()
+            ),
+            Err(e) => eprintln!("Failed to append run to benchmark history: {e}"),
+        }
+
+        // exported timings replace the README table.
+        if export.is_none() {
+            match readme_benchmarks::update(year, &timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,9 +200,81 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
+fn print_header(index: usize, puzzle: PuzzleId) {
+    if index > 0 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+    println!("------");
+}
+
+/// Runs `run` for every item on up to `jobs` threads and passes the results to `report` in the order of `items`.
+fn run_parallel<K: Copy + Sync, T: Send>(
+    items: &[K],
+    jobs: usize,
+    run: impl Fn(K) -> T + Sync,
+    mut report: impl FnMut(usize, K, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&item) = items.get(i) else {
+                    break;
+                };
+                if tx.send((i, run(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        // buffer results that finish early until all previous items have been reported.
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
+
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_report) {
+                report(next_report, items[next_report], result);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+/// A day counts as solved once answers to both parts are stored.
+fn is_solved(puzzle: PuzzleId) -> bool {
+    answers::read(puzzle).is_ok_and(|answers| answers.part_1.is_some() && answers.part_2.is_some())
+}
+
+/// Run the solution for a given puzzle from the registry, if it exists.
+fn run_in_process(puzzle: PuzzleId, registry: &[Solution], config: &RunConfig) -> Vec<PartRecord> {
+    let Some(solution) = registry.iter().find(|solution| solution.puzzle == puzzle) else {
+        return vec![];
+    };
+
+    match try_read_file("inputs", puzzle) {
+        Ok(input) => (solution.run)(&input, config),
+        Err(e) => {
+            eprintln!("Could not read input file: {e}");
+            vec![]
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    /// `cargo build` exited with the given status.
+    Build(ExitStatus),
     Parser(String),
     IO(io::Error),
 }
@@ -56,136 +285,183 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Build(status) => write!(f, "cargo build failed ({status})"),
+            Error::Parser(e) => write!(f, "could not parse result records: {e}"),
+            Error::IO(e) => write!(f, "could not invoke solution: {e}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the result records they emit.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use super::Error;
+    use crate::template::project_root;
+    use crate::template::runner::{BenchConfig, PartRecord, PARSE_PART, RESULTS_FILE_ENV};
+    use crate::PuzzleId;
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        env, fs, io,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Result of running a solution bin.
+    pub struct SolutionRun {
+        pub records: Vec<PartRecord>,
+        /// Captured output of the bin, empty if output was not captured.
+        pub stdout: Vec<u8>,
+        pub stderr: Vec<u8>,
+    }
+
+    /// Build all solution bins.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let status = Command::new("cargo").args(&args).status()?;
+
+        if !status.success() {
+            return Err(Error::Build(status));
+        }
+
+        Ok(())
+    }
+
+    /// Run the solution bin for a given puzzle and collect the result records of every part.
+    /// If `capture` is set, the output of the bin is returned instead of being forwarded to stdout/stderr.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        bench: &BenchConfig,
+        timeout: Option<Duration>,
+        capture: bool,
+    ) -> Result<SolutionRun, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !project_root().join(puzzle.bin_path()).exists() {
+            return Ok(SolutionRun {
+                records: vec![],
+                stdout: vec![],
+                stderr: vec![],
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            puzzle.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
+        }
+
+        args.push("--".into());
+
+        if let Some(timeout) = timeout {
+            args.push("--timeout".into());
+            args.push(format!("{}ns", timeout.as_nanos()));
         }
 
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            // mirror `--time` flag and bench configuration to child invocations.
+            args.push("--time".into());
+            args.append(&mut bench.to_args());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // the child appends one JSON record per part to this file, keeping results separate from display output.
+        let results_path = get_results_path(puzzle);
+        remove_results_file(&results_path)?;
+
+        let stdio = || {
+            if capture {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
 
-        let mut cmd = Command::new("cargo")
+        let output = Command::new("cargo")
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .env(RESULTS_FILE_ENV, &results_path)
+            .stdout(stdio())
+            .stderr(stdio())
+            .spawn()?
+            .wait_with_output()?;
+
+        let records = match fs::read_to_string(&results_path) {
+            Ok(contents) => parse_records(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+            Err(e) => Err(e.into()),
+        };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        remove_results_file(&results_path)?;
 
-        let mut output = vec![];
+        Ok(SolutionRun {
+            records: records?,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    fn get_results_path(puzzle: PuzzleId) -> PathBuf {
+        env::temp_dir().join(format!("advent_of_code-{}-{puzzle}.jsonl", process::id()))
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    fn remove_results_file(path: &Path) -> Result<(), Error> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
         }
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+    pub fn parse_records(contents: &str) -> Result<Vec<PartRecord>, Error> {
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| Error::Parser(e.to_string())))
+            .collect()
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    pub fn collect_timings(records: &[PartRecord], puzzle: PuzzleId) -> super::Timings {
         let mut timings = super::Timings {
-            day,
+            puzzle,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
         };
 
-        output
+        records
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+            .filter(|record| {
+                record.puzzle == puzzle && (record.part == PARSE_PART || record.answer.is_some())
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .for_each(|record| {
+                match record.part {
+                    PARSE_PART => timings.parse = Some(record.stats),
+                    1 => timings.part_1 = Some(record.stats),
+                    2 => timings.part_2 = Some(record.stats),
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += record.stats.median_nanos as f64;
+                }
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -202,53 +478,121 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::{collect_timings, parse_records};
+
+        use crate::template::runner::{PartRecord, Stats};
+        use crate::{day, year, PuzzleId};
+
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+
+        fn record(part: u8, answer: Option<&str>, median_nanos: u64) -> String {
+            serde_json::to_string(&PartRecord {
+                puzzle: PUZZLE,
+                part,
+                answer: answer.map(Into::into),
+                stats: Stats {
+                    median_nanos,
+                    samples: 10,
+                    ..Stats::default()
+                },
+                correct: None,
+                error: None,
+            })
+            .unwrap()
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let records = parse_records(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+                    record(1, Some("0"), 74),
+                    record(2, Some("10"), 74_130_000),
+                    String::new(),
+                ]
+                .join("\n"),
+            )
+            .unwrap();
+            let res = collect_timings(&records, PUZZLE);
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().median_nanos, 74);
+            assert_eq!(res.part_2.unwrap().median_nanos, 74_130_000);
         }
 
         #[test]
         fn test_patterns_in_input() {
-            let res = parse_exec_time(
+            let records = parse_records(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+                    record(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                    record(2, Some("10s\n(100ms @ 1 samples)"), 100_000_000),
+                ]
+                .join("\n"),
+            )
+            .unwrap();
+            let res = collect_timings(&records, PUZZLE);
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().median_nanos, 2_000_000_000);
+            assert_eq!(res.part_2.unwrap().median_nanos, 100_000_000);
         }
 
         #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
+        fn test_parse_step() {
+            let records = parse_records(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+                    record(0, None, 1_000),
+                    record(1, Some("1"), 10),
+                    record(2, Some("2"), 20),
+                ]
+                .join("\n"),
+            )
+            .unwrap();
+            let res = collect_timings(&records, PUZZLE);
+            assert_approx_eq!(res.total_nanos, 1030_f64);
+            assert_eq!(res.parse.unwrap().median_nanos, 1_000);
+        }
+
+        #[test]
+        fn test_missing_parts() {
+            let records =
+                parse_records(&[record(1, None, 10), record(2, None, 10)].join("\n")).unwrap();
+            let res = collect_timings(&records, PUZZLE);
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_malformed_record() {
+            assert!(parse_records("Part 1: 0 (74.13ns @ 100000 samples)").is_err());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::run_parallel;
+    use crate::{Day, DaySet};
+
+    #[test]
+    fn run_parallel_reports_in_order() {
+        let days: Vec<Day> = DaySet::all().iter().collect();
+        let mut reported = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day| {
+                // later days finish first.
+                thread::sleep(Duration::from_millis(u64::from(25 - day.into_inner())));
+                day.into_inner()
+            },
+            |i, day, result| {
+                assert_eq!(days[i], day);
+                reported.push(result);
+            },
+        );
+
+        assert_eq!(reported, (1..=25).collect::<Vec<u8>>());
     }
 }
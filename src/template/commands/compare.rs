@@ -0,0 +1,105 @@
+use std::process;
+
+use crate::template::{
+    bench_history::{self, Change, Run},
+    runner::PARSE_PART,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Year;
+
+/// Compares the latest benchmark run of a year against the previous run or the latest run saved as `baseline`.
+/// Exits with a non-zero status code if any part got slower than `threshold`.
+pub fn handle(year: Year, baseline: Option<&str>, threshold: f64) {
+    let runs = bench_history::read(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e}");
+        process::exit(1);
+    });
+
+    let Some((current, previous)) = runs.split_last() else {
+        eprintln!(
+            "No benchmark runs found in \"{}\". Run `cargo time` first.",
+            bench_history::get_path(year).display()
+        );
+        process::exit(1);
+    };
+
+    let compared = match baseline {
+        Some(name) => previous
+            .iter()
+            .rev()
+            .find(|run| run.baseline.as_deref() == Some(name)),
+        None => previous.last(),
+    };
+
+    let Some(compared) = compared else {
+        match baseline {
+            Some(name) => eprintln!("No benchmark run saved as baseline \"{name}\"."),
+            None => eprintln!("Only one benchmark run recorded, nothing to compare against."),
+        }
+        process::exit(1);
+    };
+
+    println!(
+        "Comparing {} against {}:\n",
+        describe(current),
+        describe(compared)
+    );
+
+    let changes = bench_history::compare(compared, current);
+    let regressions: Vec<&Change> = changes
+        .iter()
+        .filter(|change| change.is_regression(threshold))
+        .collect();
+
+    for change in &changes {
+        let verdict = if change.is_regression(threshold) {
+            " ✘"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {}: {:.1?} → {:.1?} ({:+.1}%){verdict}",
+            change.day,
+            format_part(change.part),
+            change.before.median(),
+            change.after.median(),
+            change.ratio() * 100.0,
+        );
+    }
+
+    if regressions.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}No part got slower than {:.1}%.{ANSI_RESET}",
+            threshold * 100.0
+        );
+    } else {
+        eprintln!(
+            "\n{ANSI_BOLD}✘ {} part(s) got slower than {:.1}%:{ANSI_RESET} {}",
+            regressions.len(),
+            threshold * 100.0,
+            regressions
+                .iter()
+                .map(|change| format!("Day {} {}", change.day, format_part(change.part)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+fn describe(run: &Run) -> String {
+    let commit = run.commit.as_deref().unwrap_or("unknown commit");
+    match &run.baseline {
+        Some(name) => format!("{ANSI_ITALIC}{name}{ANSI_RESET} ({commit})"),
+        None => commit.to_string(),
+    }
+}
+
+fn format_part(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
@@ -1,15 +1,41 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::aoc_client::{self, Client};
+use crate::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId) {
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to create client: {e}");
         process::exit(1);
+    });
+
+    let input_path = aoc_client::get_input_path(puzzle);
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
+
+    let downloads = [
+        ("input", &input_path, client.input(puzzle)),
+        ("puzzle", &puzzle_path, client.puzzle(puzzle)),
+    ];
+
+    for (name, path, contents) in downloads {
+        let contents = contents.unwrap_or_else(|e| {
+            eprintln!("Failed to download {name}: {e}");
+            process::exit(1);
+        });
+
+        if let Err(e) = write_file(path, &contents) {
+            eprintln!("Failed to write {name} to \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+
+        println!("🎄 Successfully wrote {name} to \"{}\".", path.display());
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+/// Writes a file, creating its parent directories first.
+pub fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
 }
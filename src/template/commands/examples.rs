@@ -0,0 +1,155 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    aoc_client, examples, examples::Examples, get_data_path, project_root, ANSI_BOLD, ANSI_RESET,
+};
+use crate::PuzzleId;
+
+/// Which code blocks, numbered from 1, become which example files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Selection {
+    /// Block written to `DD.txt`.
+    pub shared: Option<usize>,
+    /// Blocks written to `DD-1.txt` and `DD-2.txt`.
+    pub parts: [Option<usize>; 2],
+}
+
+impl Selection {
+    /// Writes the first block to `DD.txt` if nothing was selected.
+    fn or_default(self) -> Self {
+        if self == Self::default() {
+            Self {
+                shared: Some(1),
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, selection: Selection, list: bool, answers: bool, force: bool) {
+    let examples = read_examples(puzzle);
+
+    if examples.blocks.is_empty() {
+        eprintln!("The puzzle description contains no code blocks.");
+        process::exit(1);
+    }
+
+    if list {
+        for (index, block) in examples.blocks.iter().enumerate() {
+            println!(
+                "{ANSI_BOLD}Block {} (part {}):{ANSI_RESET}",
+                index + 1,
+                block.part
+            );
+            print!("{}", block.text);
+            println!();
+        }
+        return;
+    }
+
+    let selection = selection.or_default();
+    let targets = [
+        (selection.shared, format!("{}.txt", puzzle.day)),
+        (selection.parts[0], format!("{}-1.txt", puzzle.day)),
+        (selection.parts[1], format!("{}-2.txt", puzzle.day)),
+    ];
+
+    for (index, file_name) in targets {
+        let Some(index) = index else {
+            continue;
+        };
+
+        let Some(block) = index.checked_sub(1).and_then(|i| examples.blocks.get(i)) else {
+            eprintln!(
+                "There is no block {index}, the description contains {} block(s). Pass `--list` to show them.",
+                examples.blocks.len()
+            );
+            process::exit(1);
+        };
+
+        let path = project_root().join(get_data_path("examples", puzzle.year, &file_name));
+        if let Err(e) = write_example(&path, &block.text, force) {
+            eprintln!("Failed to write example to \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+
+        println!("Wrote block {index} to \"{}\"", path.display());
+    }
+
+    if answers {
+        insert_answers(puzzle, &examples);
+    }
+}
+
+fn read_examples(puzzle: PuzzleId) -> Examples {
+    Examples::read(puzzle).unwrap_or_else(|| {
+        eprintln!(
+            "Could not read \"{}\" or \"{}\", run `cargo download {} --year {}` first.",
+            aoc_client::get_puzzle_path(puzzle).display(),
+            examples::markdown_path(puzzle).display(),
+            puzzle.day,
+            puzzle.year
+        );
+        process::exit(1);
+    })
+}
+
+/// Writes an example file, refusing to overwrite a non-empty file with other contents unless forced.
+fn write_example(path: &Path, contents: &str, force: bool) -> Result<(), String> {
+    match fs::read_to_string(path) {
+        Ok(existing) if !force && !existing.trim().is_empty() && existing != contents => {
+            return Err(
+                "the file already has other contents, pass `--force` to overwrite it".into(),
+            );
+        }
+        _ => {}
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Replaces the placeholder assertions of the solution's tests with the emphasized answers.
+fn insert_answers(puzzle: PuzzleId, examples: &Examples) {
+    let module_path = project_root().join(puzzle.bin_path());
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        eprintln!(
+            "Could not read \"{}\", run `cargo scaffold {} --year {}` first.",
+            module_path.display(),
+            puzzle.day,
+            puzzle.year
+        );
+        process::exit(1);
+    };
+
+    for (part, answer) in (1..=2).zip(&examples.answers) {
+        let Some(answer) = answer else {
+            println!("No answer found for part {part}.");
+            continue;
+        };
+
+        if answer.parse::<i128>().is_err() {
+            println!(
+                "The answer of part {part} is not a number, add \"{answer}\" to the test by hand."
+            );
+            continue;
+        }
+
+        match examples::insert_answer(&module, part, answer) {
+            Some(updated) => {
+                module = updated;
+                println!("Expecting {answer} for part {part}");
+            }
+            None => println!("The test of part {part} already has an assertion, skipping."),
+        }
+    }
+
+    if let Err(e) = fs::write(&module_path, module) {
+        eprintln!("Failed to write \"{}\": {e}", module_path.display());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{
+    aoc_client,
+    encryption::{self, Error, Key},
+};
+use crate::{DaySet, PuzzleId, Year};
+
+/// The downloaded inputs of the selected days.
+fn input_paths(year: Year, days: &DaySet) -> impl Iterator<Item = (PuzzleId, PathBuf)> + '_ {
+    days.iter().map(move |day| {
+        let puzzle = PuzzleId::new(year, day);
+        (puzzle, aoc_client::get_input_path(puzzle))
+    })
+}
+
+/// Reads the key, generating and storing a new one if none is configured yet.
+fn read_or_create_key() -> Key {
+    match Key::from_env() {
+        Ok(key) => key,
+        Err(Error::MissingKey) => {
+            let key = Key::generate();
+            let path = encryption::key_file_path();
+            if let Err(e) = fs::write(&path, format!("{key}\n")) {
+                eprintln!("Failed to store new key in \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+            println!(
+                "Generated a new key in \"{}\". Share it with your team, but never commit it.",
+                path.display()
+            );
+            key
+        }
+        Err(e) => {
+            eprintln!("Failed to read key: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Encrypts the inputs to `DD.txt.enc`. Encrypted files that already hold the same input are left untouched,
+/// so they don't show up as changed.
+pub fn encrypt(year: Year, days: &DaySet) {
+    let key = read_or_create_key();
+    let mut found = false;
+
+    for (puzzle, path) in input_paths(year, days) {
+        // Blank inputs, e.g. created by `scaffold`, would replace the encrypted input with an empty one.
+        let Some(input) = fs::read_to_string(&path)
+            .ok()
+            .filter(|input| !input.trim().is_empty())
+        else {
+            continue;
+        };
+        found = true;
+
+        let encrypted_path = encryption::encrypted_path(&path);
+        let unchanged = fs::read(&encrypted_path)
+            .ok()
+            .and_then(|data| key.decrypt(puzzle, &data).ok())
+            .is_some_and(|existing| existing == input);
+
+        if unchanged {
+            println!("Kept \"{}\", it is up to date", encrypted_path.display());
+            continue;
+        }
+
+        if let Err(e) = fs::write(&encrypted_path, key.encrypt(puzzle, &input)) {
+            eprintln!("Failed to write \"{}\": {e}", encrypted_path.display());
+            process::exit(1);
+        }
+        println!(
+            "Encrypted \"{}\" to \"{}\"",
+            path.display(),
+            encrypted_path.display()
+        );
+    }
+
+    if !found {
+        println!("No inputs found, run `cargo download <day>` first.");
+    }
+}
+
+/// Decrypts `DD.txt.enc` to `DD.txt`. Inputs with other contents are only overwritten with `force`, blank inputs
+/// always are.
+pub fn decrypt(year: Year, days: &DaySet, force: bool) {
+    let key = Key::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to read key: {e}");
+        process::exit(1);
+    });
+    let mut found = false;
+
+    for (puzzle, path) in input_paths(year, days) {
+        let encrypted_path = encryption::encrypted_path(&path);
+        let Ok(data) = fs::read(&encrypted_path) else {
+            continue;
+        };
+        found = true;
+
+        let input = key.decrypt(puzzle, &data).unwrap_or_else(|e| {
+            eprintln!("Failed to decrypt \"{}\": {e}", encrypted_path.display());
+            process::exit(1);
+        });
+
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == input => {
+                println!("Kept \"{}\", it is up to date", path.display());
+                continue;
+            }
+            Ok(existing) if !force && !existing.trim().is_empty() => {
+                println!(
+                    "Kept \"{}\", it has other contents. Pass `--force` to overwrite it.",
+                    path.display()
+                );
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Err(e) = fs::write(&path, input) {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+        println!(
+            "Decrypted \"{}\" to \"{}\"",
+            encrypted_path.display(),
+            path.display()
+        );
+    }
+
+    if !found {
+        println!("No encrypted inputs found.");
+    }
+}
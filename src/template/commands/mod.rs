@@ -1,5 +1,9 @@
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod examples;
+pub mod inputs;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
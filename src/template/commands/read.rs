@@ -1,16 +1,26 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, Client};
+use crate::template::commands::download::write_file;
+use crate::PuzzleId;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Prints the puzzle description. The description is fetched again so that part two shows up once it is unlocked,
+/// falling back to the downloaded puzzle if the request fails.
+pub fn handle(puzzle: PuzzleId) {
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let html = match Client::from_env().and_then(|client| client.puzzle(puzzle)) {
+        Ok(html) => {
+            if let Err(e) = write_file(&puzzle_path, &html) {
+                eprintln!("Failed to update \"{}\": {e}", puzzle_path.display());
+            }
+            html
+        }
+        Err(e) => fs::read_to_string(&puzzle_path).unwrap_or_else(|_| {
+            eprintln!("Failed to fetch puzzle: {e}");
+            process::exit(1);
+        }),
     };
+
+    println!("{}", aoc_client::article_text(&html));
 }
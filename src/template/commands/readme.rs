@@ -0,0 +1,23 @@
+use std::process;
+
+use crate::template::readme_stars;
+use crate::Year;
+
+/// Fills the ⭐️ table of the readme from the stored answers and submissions.
+pub fn stars(year: Year) {
+    let progress = readme_stars::read_year(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read progress: {e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = readme_stars::update(year, &progress) {
+        eprintln!("Failed to update README with stars: {e}");
+        process::exit(1);
+    }
+
+    let stars: usize = progress
+        .iter()
+        .map(|day| day.stars.iter().filter(|&&star| star).count())
+        .sum();
+    println!("Successfully updated README with {stars} ⭐️ for {year}.");
+}
@@ -1,94 +1,201 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::Day;
-
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+use crate::template::{
+    aoc_client,
+    examples::Examples,
+    get_data_path,
+    module_template::{self, Context},
+    project_root,
+};
+use crate::PuzzleId;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// A file operation of the scaffold.
+enum Action {
+    BackupModule { from: PathBuf, to: PathBuf },
+    WriteModule { path: PathBuf, contents: String },
+    CreateFile { path: PathBuf, name: &'static str },
+    KeepFile { path: PathBuf, name: &'static str },
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
+impl Action {
+    fn describe(&self, dry_run: bool) -> String {
+        let verb = |done: &'static str, planned: &'static str| if dry_run { planned } else { done };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        match self {
+            Action::BackupModule { from, to } => {
+                format!(
+                    "{} \"{}\" to \"{}\"",
+                    verb("Backed up", "Would back up"),
+                    from.display(),
+                    to.display()
+                )
+            }
+            Action::WriteModule { path, .. } => {
+                format!(
+                    "{} module file \"{}\"",
+                    verb("Created", "Would create"),
+                    path.display()
+                )
+            }
+            Action::CreateFile { path, name } => {
+                format!(
+                    "{} empty {name} file \"{}\"",
+                    verb("Created", "Would create"),
+                    path.display()
+                )
+            }
+            Action::KeepFile { path, name } => {
+                format!(
+                    "{} existing {name} file \"{}\"",
+                    verb("Kept", "Would keep"),
+                    path.display()
+                )
+            }
+        }
+    }
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    fn path(&self) -> &Path {
+        match self {
+            Action::BackupModule { from: path, .. }
+            | Action::WriteModule { path, .. }
+            | Action::CreateFile { path, .. }
+            | Action::KeepFile { path, .. } => path,
+        }
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    fn execute(&self) -> std::io::Result<()> {
+        match self {
+            Action::BackupModule { from, to } => fs::rename(from, to),
+            Action::WriteModule { path, contents } => write_file(path, contents),
+            Action::CreateFile { path, .. } => write_file(path, ""),
+            Action::KeepFile { .. } => Ok(()),
+        }
     }
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Path for a backup of the module that does not overwrite earlier backups, e.g. `src/bin/2023-01.rs.bak.2`.
+fn backup_path(module_path: &Path) -> PathBuf {
+    let path = format!("{}.bak", module_path.display());
+    (1..)
+        .map(|n| match n {
+            1 => PathBuf::from(&path),
+            n => PathBuf::from(format!("{path}.{n}")),
+        })
+        .find(|path| !path.exists())
+        .unwrap()
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Fills the title and example answers from the puzzle description, if it was downloaded as HTML or markdown.
+fn get_context(puzzle: PuzzleId, return_type: &str, split_examples: bool) -> Context {
+    let examples = Examples::read(puzzle).unwrap_or_default();
+    let answers = examples
+        .answers
+        .map(|answer| answer.filter(|answer| answer.parse::<i128>().is_ok()));
+
+    Context {
+        puzzle,
+        title: examples.title,
+        return_type: return_type.into(),
+        answers,
+        split_examples,
+    }
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+pub fn handle(
+    puzzle: PuzzleId,
+    template: Option<&str>,
+    return_type: &str,
+    force: bool,
+    split_examples: bool,
+    dry_run: bool,
+) {
+    let template = match module_template::load(template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+    let module_path = project_root().join(puzzle.bin_path());
+    let mut actions = vec![];
+
+    if module_path.exists() {
+        if !force {
+            eprintln!(
+                "Module file \"{}\" already exists, pass `--force` to replace it.",
+                module_path.display()
+            );
             process::exit(1);
         }
+
+        actions.push(Action::BackupModule {
+            from: module_path.clone(),
+            to: backup_path(&module_path),
+        });
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    actions.push(Action::WriteModule {
+        contents: module_template::render(
+            &template,
+            &get_context(puzzle, return_type, split_examples),
+        ),
+        path: module_path,
+    });
+
+    let example_files = if split_examples {
+        vec![
+            format!("{}-1.txt", puzzle.day),
+            format!("{}-2.txt", puzzle.day),
+        ]
+    } else {
+        vec![format!("{}.txt", puzzle.day)]
+    };
+
+    let data_files = std::iter::once(("input", aoc_client::get_input_path(puzzle))).chain(
+        example_files.iter().map(|file| {
+            let path = get_data_path("examples", puzzle.year, file);
+            ("example", project_root().join(path))
+        }),
+    );
+
+    // Inputs and examples are never overwritten, not even with `--force`.
+    actions.extend(data_files.map(|(name, path)| {
+        if path.exists() {
+            Action::KeepFile { path, name }
+        } else {
+            Action::CreateFile { path, name }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    }));
+
+    for action in &actions {
+        if !dry_run {
+            if let Err(e) = action.execute() {
+                eprintln!("Failed to write \"{}\": {e}", action.path().display());
+                process::exit(1);
+            }
         }
+        println!("{}", action.describe(dry_run));
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if dry_run {
+        return;
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
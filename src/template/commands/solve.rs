@@ -1,23 +1,66 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::Day;
+use crate::template::runner::BenchConfig;
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Controls how the `solve` command runs the solution of a day.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub bench: BenchConfig,
+    /// The part whose answer is submitted.
+    pub submit: Option<u8>,
+    /// Limit for a single execution of a solution function.
+    pub timeout: Option<Duration>,
+    /// Path of a custom input, or `-` to read it from stdin.
+    pub input: Option<String>,
+    /// Runs the solution against the example files first.
+    pub examples: bool,
+}
+
+pub fn handle(puzzle: PuzzleId, options: Options) {
+    let Options {
+        is_release,
+        is_timed,
+        bench,
+        submit,
+        timeout,
+        input,
+        examples,
+    } = options;
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
-    if release {
+    if is_release {
         cmd_args.push("--release".to_string());
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit) = submit {
         cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        cmd_args.push(submit.to_string());
     }
 
-    if time {
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(format!("{}ns", timeout.as_nanos()));
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    if is_timed {
         cmd_args.push("--time".to_string());
+        cmd_args.append(&mut bench.to_args());
     }
 
     let mut cmd = Command::new("cargo")
@@ -27,5 +70,9 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
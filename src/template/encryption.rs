@@ -0,0 +1,231 @@
+/// Encryption of puzzle inputs at rest, so they can be committed without publishing them.
+///
+/// Files are encrypted with ChaCha20-Poly1305. An encrypted file starts with a version marker, followed by the random
+/// nonce and the ciphertext. The puzzle id is authenticated as associated data, so a file cannot be swapped for
+/// the input of another day without failing to decrypt.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+use crate::template::project_root;
+use crate::PuzzleId;
+
+/// Environment variable holding the key as 64 hex characters.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+
+/// Environment variable pointing to a file that holds the key.
+/// Defaults to [`DEFAULT_KEY_FILE`] in the project root.
+pub const KEY_FILE_ENV: &str = "AOC_INPUT_KEY_FILE";
+
+pub const DEFAULT_KEY_FILE: &str = ".aoc-input-key";
+
+/// Extension appended to the name of encrypted files, e.g. `05.txt.enc`.
+pub const EXTENSION: &str = "enc";
+
+/// Marks the format version of encrypted files.
+const MAGIC: &[u8] = b"aoc-enc1";
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingKey,
+    InvalidKey(String),
+    /// The file is not an encrypted input, was encrypted with another key or for another puzzle, or was modified.
+    Decrypt,
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no input key found, set `{KEY_ENV}` or store it in `{}`.",
+                key_file_path().display()
+            ),
+            Error::InvalidKey(e) => write!(f, "invalid input key: {e}"),
+            Error::Decrypt => write!(
+                f,
+                "could not decrypt, the key is wrong or the file was modified."
+            ),
+            Error::IO(e) => write!(f, "could not read key file: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A 256 bit key, shared between everyone who may read the inputs.
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Generates a random key.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Reads the key from [`KEY_ENV`], falling back to the key file.
+    pub fn from_env() -> Result<Self, Error> {
+        if let Some(key) = env::var(KEY_ENV).ok().filter(|s| !s.trim().is_empty()) {
+            return key.parse();
+        }
+
+        match fs::read_to_string(key_file_path()) {
+            Ok(key) if !key.trim().is_empty() => key.parse(),
+            Ok(_) => Err(Error::MissingKey),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::MissingKey),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+
+    #[must_use]
+    pub fn encrypt(&self, puzzle: PuzzleId, text: &str) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: text.as_bytes(),
+                    aad: puzzle.to_string().as_bytes(),
+                },
+            )
+            .expect("encryption of an in-memory buffer cannot fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, puzzle: PuzzleId, data: &[u8]) -> Result<String, Error> {
+        let data = data.strip_prefix(MAGIC).ok_or(Error::Decrypt)?;
+        if data.len() < NONCE_LEN {
+            return Err(Error::Decrypt);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let text = self
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: puzzle.to_string().as_bytes(),
+                },
+            )
+            .map_err(|_| Error::Decrypt)?;
+
+        String::from_utf8(text).map_err(|_| Error::Decrypt)
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(Error::InvalidKey(format!(
+                "expected 64 hex characters, found {}",
+                s.chars().count()
+            )));
+        }
+
+        let mut key = [0; 32];
+        for (byte, hex) in key.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let hex = std::str::from_utf8(hex).unwrap();
+            *byte = u8::from_str_radix(hex, 16)
+                .map_err(|_| Error::InvalidKey(format!("\"{hex}\" is not a hex number")))?;
+        }
+
+        Ok(Self(key))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// Path of the key file, see [`KEY_FILE_ENV`].
+#[must_use]
+pub fn key_file_path() -> PathBuf {
+    env::var_os(KEY_FILE_ENV).map_or_else(|| project_root().join(DEFAULT_KEY_FILE), PathBuf::from)
+}
+
+/// Whether a plain file is missing or holds only whitespace, like the empty input created by `scaffold`.
+/// Such a file does not hide its encrypted version.
+#[must_use]
+pub fn is_blank(path: &Path) -> bool {
+    fs::read_to_string(path).map_or(true, |text| text.trim().is_empty())
+}
+
+/// Path of the encrypted version of a file, e.g. `data/2023/inputs/05.txt.enc`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(EXTENSION);
+    PathBuf::from(path)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, Key};
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = Key::generate();
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        let data = key.encrypt(puzzle, "seeds: 79 14\n");
+
+        assert_eq!(key.decrypt(puzzle, &data).unwrap(), "seeds: 79 14\n");
+        assert_ne!(key.encrypt(puzzle, "seeds: 79 14\n"), data);
+
+        let other_day = PuzzleId::new(year!(2023), day!(6));
+        assert!(matches!(key.decrypt(other_day, &data), Err(Error::Decrypt)));
+        assert!(matches!(
+            Key::generate().decrypt(puzzle, &data),
+            Err(Error::Decrypt)
+        ));
+
+        let mut modified = data.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            key.decrypt(puzzle, &modified),
+            Err(Error::Decrypt)
+        ));
+        assert!(matches!(key.decrypt(puzzle, b"seeds"), Err(Error::Decrypt)));
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = Key::generate();
+        let parsed: Key = format!("{key}\n").parse().unwrap();
+        assert_eq!(parsed.to_string(), key.to_string());
+
+        assert!(matches!("abc".parse::<Key>(), Err(Error::InvalidKey(_))));
+        assert!(matches!(
+            "zz".repeat(32).parse::<Key>(),
+            Err(Error::InvalidKey(_))
+        ));
+    }
+}
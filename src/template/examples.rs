@@ -0,0 +1,366 @@
+/// Extracts example inputs and their expected answers from downloaded puzzle descriptions.
+use std::{fs, path::PathBuf};
+
+use crate::template::aoc_client::{self, decode_entities};
+use crate::template::{get_data_path, project_root, read_file, read_file_part};
+use crate::PuzzleId;
+
+/// A code block of a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub text: String,
+}
+
+/// The title, code blocks and emphasized example answers of a puzzle description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Title of the puzzle, e.g. `Trebuchet?!`.
+    pub title: Option<String>,
+    pub blocks: Vec<CodeBlock>,
+    /// Expected answer of the example of each part, taken from the last emphasized code of the part's description.
+    pub answers: [Option<String>; 2],
+}
+
+impl Examples {
+    /// Reads the downloaded puzzle, preferring the HTML page over a markdown description in
+    /// `data/<year>/puzzles/DD.md`. Returns [`None`] if neither exists.
+    #[must_use]
+    pub fn read(puzzle: PuzzleId) -> Option<Self> {
+        if let Ok(html) = fs::read_to_string(aoc_client::get_puzzle_path(puzzle)) {
+            return Some(Self::from_html(&html));
+        }
+
+        fs::read_to_string(markdown_path(puzzle))
+            .ok()
+            .map(|markdown| Self::from_markdown(&markdown))
+    }
+
+    /// Parses a puzzle page as downloaded by `download`, where each part is an `<article>`.
+    #[must_use]
+    pub fn from_html(html: &str) -> Self {
+        let articles: Vec<&str> = html
+            .match_indices("<article")
+            .filter_map(|(start, _)| {
+                let end = html[start..].find("</article>")?;
+                Some(&html[start..start + end])
+            })
+            .collect();
+
+        let mut examples = Self {
+            title: aoc_client::puzzle_title(html),
+            ..Self::default()
+        };
+
+        for (part, article) in (1..=2).zip(articles) {
+            examples
+                .blocks
+                .extend(
+                    sections(article, "<pre><code>", "</code></pre>").map(|block| CodeBlock {
+                        part,
+                        text: decode_entities(&strip_tags(block)),
+                    }),
+                );
+
+            examples.answers[usize::from(part - 1)] =
+                sections(article, "<code><em>", "</em></code>")
+                    .last()
+                    .map(|answer| decode_entities(&strip_tags(answer)));
+        }
+
+        examples
+    }
+
+    /// Parses a puzzle description in markdown, as written by `aoc-cli`.
+    #[must_use]
+    pub fn from_markdown(markdown: &str) -> Self {
+        let (first, second) = match markdown.find("--- Part Two ---") {
+            Some(index) => markdown.split_at(index),
+            None => (markdown, ""),
+        };
+
+        let mut examples = Self {
+            title: markdown
+                .lines()
+                .find_map(|line| line.strip_prefix("## --- Day "))
+                .and_then(|heading| heading.split_once(": "))
+                .map(|(_, title)| title.trim_end_matches('-').trim().to_string()),
+            ..Self::default()
+        };
+
+        for (part, section) in [(1, first), (2, second)] {
+            let mut in_block = false;
+            let mut text = String::new();
+
+            for line in section.lines() {
+                if line.trim_start().starts_with("```") {
+                    if in_block {
+                        examples.blocks.push(CodeBlock {
+                            part,
+                            text: std::mem::take(&mut text),
+                        });
+                    }
+                    in_block = !in_block;
+                } else if in_block {
+                    text.push_str(line);
+                    text.push('\n');
+                }
+            }
+
+            // `<code><em>` is rendered as `*...*` in backticks.
+            examples.answers[usize::from(part - 1)] = sections(section, "`*", "*`")
+                .filter(|answer| !answer.contains(['`', '*', '\n']))
+                .last()
+                .map(str::to_string);
+        }
+
+        examples
+    }
+}
+
+/// Path of a puzzle description saved as markdown by `aoc-cli`.
+#[must_use]
+pub fn markdown_path(puzzle: PuzzleId) -> PathBuf {
+    project_root().join(get_data_path(
+        "puzzles",
+        puzzle.year,
+        &format!("{}.md", puzzle.day),
+    ))
+}
+
+/// An example file of a day, either `DD.txt` or a numbered variant like `DD-2.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExampleFile {
+    pub puzzle: PuzzleId,
+    /// The number of the variant, [`None`] for `DD.txt`.
+    pub variant: Option<u8>,
+}
+
+impl ExampleFile {
+    /// Lists the example files of a day that exist, `DD.txt` first and variants in ascending order.
+    #[must_use]
+    pub fn list(puzzle: PuzzleId) -> Vec<Self> {
+        let dir = project_root().join(get_data_path("examples", puzzle.year, ""));
+        let Ok(entries) = fs::read_dir(dir) else {
+            return vec![];
+        };
+
+        let mut files: Vec<Self> = entries
+            .filter_map(|entry| Self::parse(puzzle, entry.ok()?.file_name().to_str()?))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Parses a file name like `05.txt` or `05-2.txt` of the given day.
+    fn parse(puzzle: PuzzleId, file_name: &str) -> Option<Self> {
+        let rest = file_name
+            .strip_prefix(&puzzle.day.to_string())?
+            .strip_suffix(".txt")?;
+
+        let variant = match rest.strip_prefix('-') {
+            Some(variant) => Some(variant.parse().ok()?),
+            None if rest.is_empty() => None,
+            None => return None,
+        };
+
+        Some(Self { puzzle, variant })
+    }
+
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{}-{variant}.txt", self.puzzle.day),
+            None => format!("{}.txt", self.puzzle.day),
+        }
+    }
+
+    /// The parts the file is an example for: `DD-1.txt` and `DD-2.txt` belong to one part, other files to both.
+    #[must_use]
+    pub fn parts(&self) -> &'static [u8] {
+        match self.variant {
+            Some(1) => &[1],
+            Some(2) => &[2],
+            _ => &[1, 2],
+        }
+    }
+
+    /// The expected answer of a part, if the file is the one the description's answer belongs to.
+    #[must_use]
+    pub fn expected<'a>(&self, examples: &'a Examples, part: u8) -> Option<&'a str> {
+        match self.variant {
+            None => examples.answers[usize::from(part - 1)].as_deref(),
+            Some(variant) if variant == part => examples.answers[usize::from(part - 1)].as_deref(),
+            Some(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn read(&self) -> String {
+        match self.variant {
+            Some(variant) => read_file_part("examples", self.puzzle, variant),
+            None => read_file("examples", self.puzzle),
+        }
+    }
+}
+
+/// Iterates over the text between every `start` and the following `end`.
+fn sections<'a>(s: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    s.match_indices(start).filter_map(move |(index, _)| {
+        let rest = &s[index + start.len()..];
+        rest.find(end).map(|len| &rest[..len])
+    })
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Replaces the placeholder assertion `assert_eq!(result, None);` of a part's test with the expected answer.
+/// Returns [`None`] if the test has no placeholder assertion left.
+#[must_use]
+pub fn insert_answer(module: &str, part: u8, answer: &str) -> Option<String> {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let test_name = match part {
+        1 => "fn test_part_one()",
+        _ => "fn test_part_two()",
+    };
+
+    let test_start = module.find(test_name)?;
+    let test_end = module[test_start..]
+        .find("#[test]")
+        .map_or(module.len(), |len| test_start + len);
+    let offset = test_start + module[test_start..test_end].find(PLACEHOLDER)?;
+
+    Some(format!(
+        "{}assert_eq!(result, Some({answer}));{}",
+        &module[..offset],
+        &module[offset + PLACEHOLDER.len()..]
+    ))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{insert_answer, CodeBlock, ExampleFile, Examples};
+    use crate::{day, year, PuzzleId};
+
+    const HTML: &str = r"<main>
+<article class='day-desc'><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the values are <code>12</code> and <code>38</code>. Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54630</code>.</p>
+<article class='day-desc'><h2 id='part2'>--- Part Two ---</h2>
+<pre><code>two1nine
+<em>x</em>&lt;y
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>
+</main>";
+
+    const MARKDOWN: &str = r"## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+Adding these together produces `*50*`.
+
+## --- Part Two ---
+
+```
+two1nine
+```
+
+Adding these together produces `*281*`.
+";
+
+    #[test]
+    fn extracts_examples_from_html() {
+        let examples = Examples::from_html(HTML);
+
+        assert_eq!(examples.title.as_deref(), Some("Trebuchet?!"));
+        assert_eq!(
+            examples.blocks,
+            [
+                CodeBlock {
+                    part: 1,
+                    text: "1abc2\npqr3stu8vwx\n".into()
+                },
+                CodeBlock {
+                    part: 2,
+                    text: "two1nine\nx<y\n".into()
+                },
+            ]
+        );
+        assert_eq!(examples.answers, [Some("50".into()), Some("281".into())]);
+    }
+
+    #[test]
+    fn extracts_examples_from_markdown() {
+        let examples = Examples::from_markdown(MARKDOWN);
+
+        assert_eq!(examples.title.as_deref(), Some("Trebuchet?!"));
+        assert_eq!(examples.blocks.len(), 2);
+        assert_eq!(examples.blocks[0].text, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(examples.blocks[1].part, 2);
+        assert_eq!(examples.answers, [Some("50".into()), Some("281".into())]);
+    }
+
+    #[test]
+    fn inserts_answers_into_tests() {
+        let module = "fn test_part_one() {\n    assert_eq!(result, None);\n}\n#[test]\nfn test_part_two() {\n    assert_eq!(result, None);\n}\n";
+
+        let module = insert_answer(module, 2, "281").unwrap();
+        assert_eq!(
+            module,
+            "fn test_part_one() {\n    assert_eq!(result, None);\n}\n#[test]\nfn test_part_two() {\n    assert_eq!(result, Some(281));\n}\n"
+        );
+        assert!(insert_answer(&module, 2, "281").is_none());
+        assert!(insert_answer(&module, 1, "50").is_some());
+    }
+
+    #[test]
+    fn parses_example_files() {
+        let puzzle = PuzzleId::new(year!(2023), day!(1));
+        let file = |variant| ExampleFile { puzzle, variant };
+
+        assert_eq!(ExampleFile::parse(puzzle, "01.txt"), Some(file(None)));
+        assert_eq!(ExampleFile::parse(puzzle, "01-3.txt"), Some(file(Some(3))));
+        assert_eq!(ExampleFile::parse(puzzle, "01-x.txt"), None);
+        assert_eq!(ExampleFile::parse(puzzle, "011.txt"), None);
+        assert_eq!(ExampleFile::parse(puzzle, "02.txt"), None);
+        assert_eq!(file(Some(2)).name(), "01-2.txt");
+
+        let examples = Examples {
+            title: None,
+            blocks: vec![],
+            answers: [Some("142".into()), Some("281".into())],
+        };
+        assert_eq!(file(None).parts(), [1, 2]);
+        assert_eq!(file(None).expected(&examples, 2), Some("281"));
+        assert_eq!(file(Some(2)).parts(), [2]);
+        assert_eq!(file(Some(2)).expected(&examples, 2), Some("281"));
+        assert_eq!(file(Some(3)).expected(&examples, 1), None);
+    }
+}
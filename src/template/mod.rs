@@ -1,48 +1,321 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
 pub mod commands;
+pub mod encryption;
+pub mod examples;
+pub mod module_template;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod submissions;
+pub mod timings_export;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Path of a file in a data folder of a year, e.g. `data/2023/inputs/05.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn get_data_path(folder: &str, year: Year, file_name: &str) -> String {
+    format!("data/{year}/{folder}/{file_name}")
+}
+
+/// An error returned when a data file cannot be read, naming the path that was tried.
+#[derive(Debug)]
+pub struct ReadFileError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read \"{}\": {}",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Locates the project root by walking up from the current directory to the first directory with the
+/// `Cargo.toml` of this package. Falls back to the directory this crate was built from, so binaries work from any
+/// directory, including other Cargo projects.
+#[must_use]
+pub fn project_root() -> PathBuf {
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            cwd.ancestors()
+                .find(|dir| is_project_root(dir))
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+}
+
+/// Whether the `Cargo.toml` in a directory belongs to this package.
+fn is_project_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| {
+            manifest
+                .get("package")
+                .and_then(|package| package.get("name"))
+                .and_then(toml::Value::as_str)
+                == Some(env!("CARGO_PKG_NAME"))
+        })
+}
+
+/// Normalizes puzzle text: CRLF line endings become LF and trailing line endings are removed.
+#[must_use]
+pub fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Reads a text file from a path, see [`normalize`].
+pub fn read_path(path: impl AsRef<Path>) -> Result<String, ReadFileError> {
+    let path = path.as_ref();
+    fs::read_to_string(path)
+        .map(|text| normalize(&text))
+        .map_err(|source| ReadFileError {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Reads a data file of a puzzle. If the file is missing or blank and its encrypted version `<path>.enc` exists,
+/// that is decrypted with the key from [`encryption::Key::from_env`].
+fn read_data_file(path: PathBuf, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+    read_data_file_with_key(path, puzzle, encryption::Key::from_env)
+}
+
+fn read_data_file_with_key(
+    path: PathBuf,
+    puzzle: PuzzleId,
+    key: impl FnOnce() -> Result<encryption::Key, encryption::Error>,
+) -> Result<String, ReadFileError> {
+    let encrypted = encryption::encrypted_path(&path);
+    if !encrypted.exists() || !encryption::is_blank(&path) {
+        return read_path(path);
+    }
+
+    let data = fs::read(&encrypted).map_err(|source| ReadFileError {
+        path: encrypted.clone(),
+        source,
+    })?;
+
+    key()
+        .and_then(|key| key.decrypt(puzzle, &data))
+        .map(|text| normalize(&text))
+        .map_err(|e| ReadFileError {
+            path: encrypted,
+            source: io::Error::new(io::ErrorKind::InvalidData, e),
+        })
+}
+
+/// Reads a data file of a puzzle relative to the [`project_root`], e.g. `data/2023/inputs/05.txt`.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+    read_data_file(
+        project_root().join(get_data_path(
+            folder,
+            puzzle.year,
+            &format!("{}.txt", puzzle.day),
+        )),
+        puzzle,
+    )
+}
+
+/// Reads a data file of a puzzle with a part suffix relative to the [`project_root`], e.g. `01-2.txt`.
+pub fn try_read_file_part(
+    folder: &str,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Result<String, ReadFileError> {
+    read_data_file(
+        project_root().join(get_data_path(
+            folder,
+            puzzle.year,
+            &format!("{}-{part}.txt", puzzle.day),
+        )),
+        puzzle,
+    )
+}
+
+/// Helper function that reads a text file to a string. Panics if the file cannot be read, see [`try_read_file`].
+#[must_use]
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Panics if the file cannot be read, see [`try_read_file_part`].
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    try_read_file_part(folder, puzzle, part).unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+///
+/// If a parse function is passed as third argument, it is run (and timed) once and both parts receive a reference to its output.
+/// A parse function returning a [`Result`] is passed as `try_parse = <function>`. If it fails, the error is reported as the
+/// result of the parse step and the parts are skipped.
+///
+/// Besides `main`, this creates a `run` function and a `SOLUTION` constant which allow the `all` command to run the day in-process.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+    (@common $year:literal, $day:literal) => {
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId =
+            advent_of_code::PuzzleId::new(advent_of_code::year!($year), advent_of_code::day!($day));
+
+        /// Registry entry of this solution.
+        pub const SOLUTION: advent_of_code::template::runner::Solution =
+            advent_of_code::template::runner::Solution {
+                puzzle: PUZZLE,
+                run,
+            };
 
         fn main() {
+            advent_of_code::template::runner::main(SOLUTION);
+        }
+    };
+    ($year:literal, $day:literal, try_parse = $parse:expr) => {
+        $crate::solution!(@common $year, $day);
+
+        /// Runs the parse step and, if it succeeds, both parts against an input.
+        pub fn run(
+            input: &str,
+            config: &advent_of_code::template::runner::RunConfig,
+        ) -> Vec<advent_of_code::template::runner::PartRecord> {
+            use advent_of_code::template::runner::*;
+            let input: std::sync::Arc<str> = input.into();
+            let parsed = match run_parse($parse, &input, PUZZLE, config) {
+                Ok(parsed) => parsed,
+                Err(record) => return vec![record],
+            };
+            vec![
+                run_part(part_one, &parsed, PUZZLE, 1, config),
+                run_part(part_two, &parsed, PUZZLE, 2, config),
+            ]
+        }
+    };
+    ($year:literal, $day:literal, $parse:expr) => {
+        $crate::solution!(@common $year, $day);
+
+        /// Runs the parse step and both parts against an input.
+        pub fn run(
+            input: &str,
+            config: &advent_of_code::template::runner::RunConfig,
+        ) -> Vec<advent_of_code::template::runner::PartRecord> {
+            use advent_of_code::template::runner::*;
+            let input: std::sync::Arc<str> = input.into();
+            let parse = |input: &str| Ok::<_, std::convert::Infallible>($parse(input));
+            let parsed = match run_parse(parse, &input, PUZZLE, config) {
+                Ok(parsed) => parsed,
+                Err(record) => return vec![record],
+            };
+            vec![
+                run_part(part_one, &parsed, PUZZLE, 1, config),
+                run_part(part_two, &parsed, PUZZLE, 2, config),
+            ]
+        }
+    };
+    ($year:literal, $day:literal) => {
+        $crate::solution!(@common $year, $day);
+
+        /// Runs both parts against an input.
+        pub fn run(
+            input: &str,
+            config: &advent_of_code::template::runner::RunConfig,
+        ) -> Vec<advent_of_code::template::runner::PartRecord> {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input: std::sync::Arc<str> = input.into();
+            vec![
+                run_part(part_one, &input, PUZZLE, 1, config),
+                run_part(part_two, &input, PUZZLE, 2, config),
+            ]
         }
     };
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::{day, year};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+        assert_eq!(normalize("a  "), "a  ");
+    }
+
+    #[test]
+    fn finds_project_root_by_package_name() {
+        let dir = env::temp_dir().join(format!("aoc-root-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"other\"\n").unwrap();
+
+        assert!(is_project_root(Path::new(env!("CARGO_MANIFEST_DIR"))));
+        assert!(!is_project_root(&dir));
+        assert!(!is_project_root(&dir.join("missing")));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn decrypts_over_blank_scaffolded_file() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        let dir = env::temp_dir().join(format!("aoc-read-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("05.txt");
+
+        let key = encryption::Key::generate();
+        fs::write(
+            encryption::encrypted_path(&path),
+            key.encrypt(puzzle, "seeds: 79\r\n"),
+        )
+        .unwrap();
+        let read = |path: &Path| {
+            let key = key.to_string();
+            read_data_file_with_key(path.to_path_buf(), puzzle, move || key.parse())
+        };
+
+        // `scaffold` creates an empty input, which must not hide the encrypted one.
+        fs::write(&path, "\n").unwrap();
+        assert_eq!(read(&path).unwrap(), "seeds: 79");
+
+        fs::write(&path, "seeds: 14\n").unwrap();
+        assert_eq!(read(&path).unwrap(), "seeds: 14");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_errors_name_the_path() {
+        let puzzle = PuzzleId::new(year!(2023), day!(25));
+        let error = try_read_file_part("missing", puzzle, 7).unwrap_err();
+
+        assert!(error.path.is_absolute());
+        assert!(error.path.ends_with("data/2023/missing/25-7.txt"));
+        assert!(error
+            .to_string()
+            .starts_with(&format!("could not read \"{}\"", error.path.display())));
+    }
+}
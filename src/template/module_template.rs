@@ -0,0 +1,225 @@
+/// Templates for the solution modules created by `scaffold`.
+/// Templates are loaded from `templates/<name>.rs`, falling back to [`BUILTIN`] if there is no `default` template.
+use std::{fmt::Display, fs, io};
+
+use crate::template::project_root;
+use crate::PuzzleId;
+
+pub const TEMPLATE_DIR: &str = "templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_RETURN_TYPE: &str = "u32";
+
+pub const BUILTIN: &str = r#"advent_of_code::solution!({{year}}, {{day}});
+
+pub fn part_one(_input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+pub fn part_two(_input: &str) -> Option<{{return_type}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&{{part_one_example}});
+        assert_eq!(result, {{part_one_answer}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&{{part_two_example}});
+        assert_eq!(result, {{part_two_answer}});
+    }
+}
+"#;
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name) => {
+                write!(f, "template \"{name}\" not found")?;
+                let available = available();
+                if !available.is_empty() {
+                    write!(f, ", available templates: {}", available.join(", "))?;
+                }
+                Ok(())
+            }
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+/// Values of the placeholders of a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context {
+    pub puzzle: PuzzleId,
+    /// Puzzle title, if the description was downloaded.
+    pub title: Option<String>,
+    pub return_type: String,
+    /// Expected answers for the examples, if known.
+    pub answers: [Option<String>; 2],
+    /// Whether the tests read the part-specific examples `DD-1.txt` and `DD-2.txt`.
+    pub split_examples: bool,
+}
+
+impl Context {
+    fn placeholders(&self) -> [(&'static str, String); 8] {
+        let answer = |answer: &Option<String>| {
+            answer
+                .as_ref()
+                .map_or_else(|| "None".into(), |answer| format!("Some({answer})"))
+        };
+
+        let example = |part: u8| {
+            if self.split_examples {
+                format!("advent_of_code::template::read_file_part(\"examples\", PUZZLE, {part})")
+            } else {
+                "advent_of_code::template::read_file(\"examples\", PUZZLE)".into()
+            }
+        };
+
+        [
+            ("year", self.puzzle.year.to_string()),
+            ("day", self.puzzle.day.into_inner().to_string()),
+            (
+                "title",
+                self.title
+                    .clone()
+                    .unwrap_or_else(|| format!("Day {}", self.puzzle.day)),
+            ),
+            ("return_type", self.return_type.clone()),
+            ("part_one_answer", answer(&self.answers[0])),
+            ("part_two_answer", answer(&self.answers[1])),
+            ("part_one_example", example(1)),
+            ("part_two_example", example(2)),
+        ]
+    }
+}
+
+/// Replaces the `{{placeholder}}`s of a template.
+#[must_use]
+pub fn render(template: &str, context: &Context) -> String {
+    context
+        .placeholders()
+        .iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+/// Loads a template by name. A missing `default` template yields the built-in one.
+pub fn load(name: Option<&str>) -> Result<String, Error> {
+    let name = name.unwrap_or(DEFAULT_TEMPLATE);
+
+    match fs::read_to_string(project_root().join(TEMPLATE_DIR).join(format!("{name}.rs"))) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if name == DEFAULT_TEMPLATE {
+                Ok(BUILTIN.into())
+            } else {
+                Err(Error::NotFound(name.into()))
+            }
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Names of the templates in the template directory.
+#[must_use]
+pub fn available() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(project_root().join(TEMPLATE_DIR)) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "rs").then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Context, BUILTIN};
+    use crate::{day, year, PuzzleId};
+
+    fn context() -> Context {
+        Context {
+            puzzle: PuzzleId::new(year!(2023), day!(6)),
+            title: Some("Wait For It".into()),
+            return_type: "u64".into(),
+            answers: [Some("288".into()), None],
+            split_examples: false,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "//! {{title}} ({{year}}-{{day}})\nfn f() -> {{return_type}} {}\n{{part_one_answer}} {{part_two_answer}} {{unknown}}",
+            &context(),
+        );
+
+        assert_eq!(
+            rendered,
+            "//! Wait For It (2023-6)\nfn f() -> u64 {}\nSome(288) None {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let rendered = render(
+            BUILTIN,
+            &Context {
+                title: None,
+                ..context()
+            },
+        );
+
+        assert!(rendered.starts_with("advent_of_code::solution!(2023, 6);"));
+        assert!(rendered.contains("pub fn part_one(_input: &str) -> Option<u64>"));
+        assert!(rendered.contains("assert_eq!(result, Some(288));"));
+        assert!(rendered.contains("assert_eq!(result, None);"));
+        assert!(rendered
+            .contains("part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE))"));
+        assert!(!rendered.contains("{{"));
+    }
+
+    #[test]
+    fn renders_split_examples() {
+        let rendered = render(
+            BUILTIN,
+            &Context {
+                split_examples: true,
+                ..context()
+            },
+        );
+
+        assert!(rendered.contains(
+            "part_one(&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 1))"
+        ));
+        assert!(rendered.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2))"
+        ));
+    }
+}
@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use serde::Serialize;
+
+use crate::template::{project_root, runner::Stats};
+use crate::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -18,11 +21,22 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Clone)]
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "could not access README: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
 pub struct Timings {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    #[serde(flatten)]
+    pub puzzle: PuzzleId,
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
 }
 
@@ -31,18 +45,13 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -58,123 +67,153 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Renders the timings as a markdown table, with a heading of the given level, e.g. `##`.
+#[must_use]
+pub fn construct_table(prefix: &str, year: Year, timings: &[Timings], total_millis: f64) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| [Day {}](./{}) | `{}` | `{}` | `{}` |",
+            timing.puzzle.day.into_inner(),
+            timing.puzzle.bin_path(),
+            format_timing(timing.parse.as_ref()),
+            format_timing(timing.part_1.as_ref()),
+            format_timing(timing.part_2.as_ref())
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn format_timing(stats: Option<&Stats>) -> String {
+    stats.map_or_else(|| "-".into(), |stats| format!("{:.1?}", stats.median()))
+}
+
+/// Replaces the table between the two occurrences of `marker`, keeping the markers.
+pub fn replace_table(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &format!("{marker}\n{table}\n{marker}"),
+    );
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+/// Replaces the table between the two occurrences of `marker` in the readme.
+pub fn update_table(marker: &str, table: &str) -> Result<(), Error> {
+    let path = project_root().join("README.md");
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
+    replace_table(&mut readme, marker, table)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+pub fn update(year: Year, timings: &[Timings], total_millis: f64) -> Result<(), Error> {
+    update_table(MARKER, &construct_table("##", year, timings, total_millis))
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{construct_table, replace_table, Timings, MARKER};
+    use crate::template::runner::Stats;
+    use crate::{day, year, PuzzleId};
+
+    fn stats(median_millis: u64) -> Option<Stats> {
+        Some(Stats {
+            median_nanos: median_millis * 1_000_000,
+            ..Stats::default()
+        })
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                parse: None,
+                part_1: stats(10),
+                part_2: stats(20),
                 total_nanos: 3e+10,
             },
             Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(2)),
+                parse: None,
+                part_1: stats(30),
+                part_2: stats(40),
                 total_nanos: 7e+10,
             },
             Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                puzzle: PuzzleId::new(year!(2023), day!(4)),
+                parse: stats(5),
+                part_1: stats(40),
+                part_2: stats(50),
                 total_nanos: 9e+10,
             },
         ]
     }
 
+    fn table() -> String {
+        construct_table("##", year!(2023), &get_mock_timings(), 190.0)
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        replace_table(&mut s, MARKER, &table()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        replace_table(&mut s, MARKER, &table()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        replace_table(&mut s, MARKER, &table()).unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        replace_table(&mut s, MARKER, &table()).unwrap();
+        replace_table(&mut s, MARKER, &table()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        replace_table(&mut s, MARKER, &table()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## 2023 Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `-` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `5.0ms` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
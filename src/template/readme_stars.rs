@@ -0,0 +1,139 @@
+/// Module that fills the readme's ⭐️ table from the stored answers and submissions, in the format of the
+/// `advent-readme-stars` action.
+use crate::template::readme_benchmarks::{self, Error};
+use crate::template::submissions::Outcome;
+use crate::template::{answers, aoc_client, project_root, submissions};
+use crate::{all_days, PuzzleId, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The solved parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub puzzle: PuzzleId,
+    pub stars: [bool; 2],
+    /// Whether `src/bin` contains a solution for the day.
+    pub has_solution: bool,
+}
+
+impl Progress {
+    /// A part is solved if its answer is stored or a submission was correct. Missing files count as empty, other
+    /// errors reading them are returned with the path of the file.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        let answers = answers::read(puzzle)
+            .map_err(|e| format!("\"{}\": {e}", answers::get_path(puzzle).display()))?;
+        let submissions = submissions::read(puzzle)
+            .map_err(|e| format!("\"{}\": {e}", submissions::get_path(puzzle).display()))?;
+
+        let stars = [1, 2].map(|part| {
+            answers.get(part).is_some()
+                || submissions
+                    .submissions
+                    .iter()
+                    .any(|s| s.part == part && s.outcome == Outcome::Correct)
+        });
+
+        Ok(Self {
+            puzzle,
+            stars,
+            has_solution: project_root().join(puzzle.bin_path()).exists(),
+        })
+    }
+}
+
+/// Reads the progress of every day of a year that has a solution or a star, see [`Progress::read`].
+pub fn read_year(year: Year) -> Result<Vec<Progress>, String> {
+    all_days()
+        .map(|day| Progress::read(PuzzleId::new(year, day)))
+        .filter(|progress| {
+            progress.as_ref().map_or(true, |progress| {
+                progress.has_solution || progress.stars.contains(&true)
+            })
+        })
+        .collect()
+}
+
+/// Renders the progress as a markdown table, with a heading of the given level, e.g. `##`.
+#[must_use]
+pub fn construct_table(prefix: &str, year: Year, progress: &[Progress]) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Solution | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        let solution = if day.has_solution {
+            format!("[Solution](./{})", day.puzzle.bin_path())
+        } else {
+            "-".into()
+        };
+        let [part_1, part_2] = day.stars.map(|star| if star { "⭐" } else { " " });
+
+        lines.push(format!(
+            "| [Day {}]({}) | {solution} | {part_1} | {part_2} |",
+            day.puzzle.day.into_inner(),
+            aoc_client::get_puzzle_url(day.puzzle),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn update(year: Year, progress: &[Progress]) -> Result<(), Error> {
+    readme_benchmarks::update_table(MARKER, &construct_table("##", year, progress))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, Progress, MARKER};
+    use crate::template::readme_benchmarks::replace_table;
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn format_stars() {
+        let progress = [
+            Progress {
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                stars: [true, true],
+                has_solution: true,
+            },
+            Progress {
+                puzzle: PuzzleId::new(year!(2023), day!(2)),
+                stars: [true, false],
+                has_solution: true,
+            },
+            Progress {
+                puzzle: PuzzleId::new(year!(2023), day!(3)),
+                stars: [true, false],
+                has_solution: false,
+            },
+        ];
+
+        let mut s = format!("# readme\n{MARKER}\n## 2023 Results\n{MARKER}\n\nfoo");
+        replace_table(
+            &mut s,
+            MARKER,
+            &construct_table("##", year!(2023), &progress),
+        )
+        .unwrap();
+
+        let expected = [
+            "# readme",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Solution | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | [Solution](./src/bin/2023-01.rs) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | [Solution](./src/bin/2023-02.rs) | ⭐ |   |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | - | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "foo",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
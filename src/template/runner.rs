@@ -1,103 +1,1053 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use crate::template::aoc_client::{self, Client, Verdict};
+use crate::template::examples::{ExampleFile, Examples};
+use crate::template::{
+    answers, get_data_path, normalize, project_root, read_path, submissions, try_read_file,
+    ReadFileError, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::OpenOptions;
+use std::io::{self, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Environment variable that, when set to a file path, makes [`run_part`] append a
+/// [`PartRecord`] for every executed part to that file as a line of JSON.
+pub const RESULTS_FILE_ENV: &str = "AOC_RESULTS_FILE";
+
+/// Part number used in a [`PartRecord`] for the parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// Stack size of the threads running solutions when a timeout is set, matching the usual main thread.
+const THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Machine-readable result of a single solution part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartRecord {
+    #[serde(flatten)]
+    pub puzzle: PuzzleId,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    /// The displayed answer, [`None`] if the part returned no result.
+    pub answer: Option<String>,
+    pub stats: Stats,
+    /// Whether the answer matches the one stored in `data/answers`, [`None`] if no answer is stored.
+    #[serde(default)]
+    pub correct: Option<bool>,
+    /// The error returned by the part, including its sources.
+    #[serde(default)]
+    pub error: Option<String>,
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Return types accepted from solution parts.
+///
+/// Parts either return an [`Option`], where [`None`] means there is no answer yet, or a [`Result`].
+/// Any [`Error`] type can be used for the latter, as well as plain `String` or `&str` messages.
+pub trait PartOutput {
+    /// Converts the output into the displayed answer, [`None`] if there is no answer.
+    fn into_answer(self) -> Result<Option<String>, ErrorChain>;
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+impl<T: Display> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, ErrorChain> {
+        Ok(self.map(|answer| answer.to_string()))
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    fn into_answer(self) -> Result<Option<String>, ErrorChain> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(ErrorChain::new(&*e.into())),
+        }
+    }
+}
 
-    hook(&result);
+/// Messages of an error and each of its sources, outermost first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorChain(Vec<String>);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+impl ErrorChain {
+    #[must_use]
+    pub fn new(error: &dyn Error) -> Self {
+        let mut messages = vec![error.to_string()];
+        let mut source = error.source();
+
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+
+        Self(messages)
+    }
+
+    #[must_use]
+    pub fn messages(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl Display for ErrorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join(": "))
+    }
+}
+
+/// Reasons a solution function did not return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Failure {
+    Panicked {
+        message: String,
+        location: Option<String>,
+    },
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panicked {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked: {message} (at {location})"),
+            Failure::Panicked {
+                message,
+                location: None,
+            } => write!(f, "panicked: {message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+        }
+    }
+}
+
+impl From<Failure> for ErrorChain {
+    fn from(failure: Failure) -> Self {
+        Self(vec![failure.to_string()])
+    }
+}
+
+/// A day's solution that can be run in-process. Created by the [`solution!`](crate::solution) macro.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs all parts of the solution against an input.
+    pub run: fn(&str, &RunConfig) -> Vec<PartRecord>,
+}
+
+/// Summary statistics over the measured executions of a solution part, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_nanos: u64,
+    pub median_nanos: u64,
+    pub min_nanos: u64,
+    pub max_nanos: u64,
+    pub p95_nanos: u64,
+    pub stddev_nanos: f64,
+    pub samples: u64,
+    /// Number of samples outside the 1.5 IQR fences. These are excluded from mean and standard deviation.
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Computes statistics over a set of measured durations.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let mut sorted: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3.saturating_sub(q1);
+        let lower_fence = q1.saturating_sub(iqr + iqr / 2);
+        let upper_fence = q3.saturating_add(iqr + iqr / 2);
+
+        let inliers: Vec<u64> = sorted
+            .iter()
+            .copied()
+            .filter(|&n| n >= lower_fence && n <= upper_fence)
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mean = inliers.iter().map(|&n| n as f64).sum::<f64>() / inliers.len() as f64;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = inliers
+            .iter()
+            .map(|&n| (n as f64 - mean).powi(2))
+            .sum::<f64>()
+            / inliers.len() as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            mean_nanos: mean.round() as u64,
+            median_nanos: percentile(&sorted, 0.5),
+            min_nanos: sorted[0],
+            max_nanos: sorted[sorted.len() - 1],
+            p95_nanos: percentile(&sorted, 0.95),
+            stddev_nanos: variance.sqrt(),
+            samples: sorted.len() as u64,
+            outliers: (sorted.len() - inliers.len()) as u64,
+        }
+    }
+
+    #[must_use]
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_nanos)
+    }
+
+    #[must_use]
+    pub fn stddev(&self) -> Duration {
+        Duration::from_secs_f64(self.stddev_nanos / 1e9)
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Controls how solution parts are benched when running with `--time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent executing the solution before measurements are taken.
+    pub warmup: Duration,
+    /// Approximate total time spent taking measurements.
+    pub budget: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads the bench configuration from command-line arguments, falling back to defaults for missing options.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        if let Some(warmup) = arg_value(args, "--warmup") {
+            config.warmup = parse_duration(warmup)?;
+        }
+
+        if let Some(budget) = arg_value(args, "--bench-time") {
+            config.budget = parse_duration(budget)?;
+        }
+
+        if let Some(min_samples) = arg_value(args, "--min-samples") {
+            config.min_samples = min_samples
+                .parse()
+                .map_err(|_| format!("invalid sample count: {min_samples}"))?;
+        }
+
+        if let Some(max_samples) = arg_value(args, "--max-samples") {
+            config.max_samples = max_samples
+                .parse()
+                .map_err(|_| format!("invalid sample count: {max_samples}"))?;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that benching takes at least one sample within a non-zero time budget.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.budget.is_zero() {
+            return Err("expecting --bench-time > 0".into());
+        }
+
+        if self.max_samples == 0 {
+            return Err("expecting --max-samples > 0".into());
+        }
+
+        if self.min_samples == 0 || self.min_samples > self.max_samples {
+            return Err(format!(
+                "expecting 0 < --min-samples <= --max-samples, got {} and {}",
+                self.min_samples, self.max_samples
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Converts the configuration into arguments understood by [`BenchConfig::from_args`].
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--warmup".into(),
+            format!("{}ns", self.warmup.as_nanos()),
+            "--bench-time".into(),
+            format!("{}ns", self.budget.as_nanos()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+/// Controls how solution functions are executed by [`run_part`] and [`run_parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Limit for a single execution of a solution function.
+    pub timeout: Option<Duration>,
+    /// Bench configuration when timing, functions are executed once without it.
+    pub bench: Option<BenchConfig>,
+    /// Set while running against example files, see [`run_examples`]. Results are then neither printed, recorded,
+    /// checked against stored answers nor submitted.
+    pub examples: bool,
+    /// The input the solution runs against, stored answers are only checked for the puzzle input.
+    pub input: InputSource,
+    /// The part whose answer is submitted, selected with `--submit <part>`.
+    pub submit: Option<u8>,
+}
+
+impl RunConfig {
+    /// Reads `--timeout`, `--input`, `--submit` and, if `--time` is passed, the bench configuration from
+    /// command-line arguments.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let timeout = arg_value(args, "--timeout")
+            .map(parse_duration)
+            .transpose()?;
+
+        let bench = if args.iter().any(|x| x == "--time") {
+            Some(BenchConfig::from_args(args)?)
+        } else {
+            None
+        };
+
+        let submit = if args.iter().any(|x| x == "--submit") {
+            let part = arg_value(args, "--submit").and_then(|part| part.parse().ok());
+            Some(part.ok_or("`--submit` expects a part number, e.g. `cargo solve 1 --submit 1`")?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            timeout,
+            bench,
+            examples: false,
+            input: InputSource::from_args(args),
+            submit,
+        })
+    }
+}
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let index = args.iter().position(|x| x == flag)?;
+    args.get(index + 1).map(String::as_str)
+}
+
+/// Parses a duration with a unit suffix, e.g. `500ms`, `1.5s` or `2m`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(unit_start);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration: {s}"))?;
+
+    let seconds = match unit {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" => value,
+        "m" => value * 60.0,
+        _ => {
+            return Err(format!(
+                "invalid duration unit in \"{s}\", expecting one of ns, us, ms, s, m"
+            ))
+        }
     };
 
-    (result, run.0, run.1)
+    Ok(Duration::from_secs_f64(seconds))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Where a solution binary reads its input from, selected with `--input <path>` or `--input -`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The downloaded input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    File(String),
+    Stdin,
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+impl InputSource {
+    pub fn from_args(args: &[String]) -> Self {
+        match arg_value(args, "--input") {
+            None => InputSource::Puzzle,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.into()),
+        }
+    }
+
+    /// Whether the input is not the puzzle input, so stored answers do not apply to it.
+    pub fn is_custom(&self) -> bool {
+        *self != InputSource::Puzzle
+    }
+
+    /// Reads the input, see [`normalize`].
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+        match self {
+            InputSource::Puzzle => try_read_file("inputs", puzzle),
+            InputSource::File(path) => read_path(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                match io::stdin().read_to_string(&mut input) {
+                    Ok(_) => Ok(normalize(&input)),
+                    Err(source) => Err(ReadFileError {
+                        path: "stdin".into(),
+                        source,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Read the input selected by the [`RunConfig`], exiting with a message if it cannot be read.
+pub fn read_input(puzzle: PuzzleId, config: &RunConfig) -> String {
+    let source = &config.input;
+
+    if source.is_custom() && config.submit.is_some() {
+        eprintln!("Answers for a custom input cannot be submitted, remove `--input` to submit.");
+        process::exit(1);
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
+    source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        if *source == InputSource::Puzzle && e.source.kind() == io::ErrorKind::NotFound {
+            eprintln!(
+                "Run `cargo download {} --year {}` or pass `--input <path>`.",
+                puzzle.day, puzzle.year
+            );
+        }
+        process::exit(1);
+    })
+}
+
+/// Run a solution part, capturing panics and enforcing the timeout of the [`RunConfig`].
+pub fn run_part<I, T, F>(
+    func: F,
+    input: &Arc<I>,
+    puzzle: PuzzleId,
+    part: u8,
+    config: &RunConfig,
+) -> PartRecord
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: PartOutput + Send + 'static,
+    F: Fn(&I) -> T + Copy + Send + 'static,
+{
+    let part_str = format!("Part {part}");
+
+    let (result, stats) = run_timed(func, input, config, |result| {
+        let result = result.into_answer();
+        if !config.examples {
+            print_result(&result, &part_str, "");
+        }
+        result
+    });
+
+    let result = result.unwrap_or_else(|failure| Err(failure.into()));
+
+    let (answer, error) = match &result {
+        Ok(answer) => (answer.clone(), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    if config.examples {
+        return PartRecord {
+            puzzle,
+            part,
+            answer,
+            stats,
+            correct: None,
+            error,
+        };
+    }
+
+    // Stored answers belong to the puzzle input and are not checked for custom inputs.
+    let stored = if config.input.is_custom() {
+        answers::Answers::default()
+    } else {
+        answers::read(puzzle).unwrap_or_else(|e| {
+            eprintln!("Failed to read stored answers: {e}");
+            answers::Answers::default()
+        })
+    };
+    let correct = stored.verify(part, answer.as_deref());
+
+    print_result(
+        &result,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_stats(&stats),
+            format_verdict(correct, stored.get(part))
         ),
     );
 
-    let mut timers: Vec<Duration> = vec![];
+    let record = PartRecord {
+        puzzle,
+        part,
+        answer,
+        stats,
+        correct,
+        error,
+    };
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        func(cloned);
-        timers.push(timer.elapsed());
+    emit_record(&record);
+
+    if let Some(answer) = record
+        .answer
+        .as_ref()
+        .filter(|_| config.submit == Some(part))
+    {
+        match submit_result(answer, puzzle, part) {
+            Some(Ok(Verdict::Correct)) => match answers::store(puzzle, part, answer) {
+                Ok(()) => println!(
+                    "Stored answer in \"{}\".",
+                    answers::get_path(puzzle).display()
+                ),
+                Err(e) => eprintln!("Failed to store answer: {e}"),
+            },
+            Some(Err(e)) => eprintln!("Failed to submit answer: {e}"),
+            _ => {}
+        }
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    record
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
+/// Entry point of a solution binary. Runs the solution against the input selected by the command-line
+/// arguments, after running it against the example files if `--examples` is passed.
+pub fn main(solution: Solution) {
+    let Solution { puzzle, run } = solution;
+    let args: Vec<String> = env::args().collect();
+    let config = RunConfig::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}");
+        process::exit(1);
+    });
+
+    let examples_correct = if args.iter().any(|x| x == "--examples") {
+        let correct = run_examples(puzzle, run, &config);
+        println!("---");
+        correct
+    } else {
+        true
+    };
+
+    let input = read_input(puzzle, &config);
+    exit_on_mismatch(&run(&input, &config));
+
+    if !examples_correct {
+        process::exit(1);
+    }
+}
+
+/// A part run against an example file, compared to the answer in the puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleResult {
+    pub file: String,
+    pub part: u8,
+    /// The answer, or the error if the part failed.
+    pub result: Result<Option<String>, String>,
+    pub expected: Option<String>,
+}
+
+impl ExampleResult {
+    /// Whether the answer matches the expected one, [`None`] if no answer is expected.
+    pub fn correct(&self) -> Option<bool> {
+        let expected = self.expected.as_deref()?;
+        Some(
+            self.result
+                .as_ref()
+                .is_ok_and(|answer| answer.as_deref() == Some(expected)),
+        )
+    }
+}
+
+/// Run a solution against every example file of the day and print the results next to the expected answers.
+/// Examples are never benched. Returns whether all answers that have an expected value match it.
+pub fn run_examples(
+    puzzle: PuzzleId,
+    run: fn(&str, &RunConfig) -> Vec<PartRecord>,
+    config: &RunConfig,
+) -> bool {
+    let files = ExampleFile::list(puzzle);
+    if files.is_empty() {
+        println!(
+            "No example files found in \"{}\".",
+            project_root()
+                .join(get_data_path("examples", puzzle.year, ""))
+                .display()
+        );
+        return true;
+    }
+
+    let examples = &Examples::read(puzzle).unwrap_or_default();
+    let config = &RunConfig {
+        bench: None,
+        examples: true,
+        ..config.clone()
+    };
+
+    let results: Vec<ExampleResult> = files
+        .iter()
+        .flat_map(|file| {
+            let records = run(&file.read(), config);
+            file.parts().iter().map(move |&part| ExampleResult {
+                file: file.name(),
+                part,
+                result: example_result(&records, part),
+                expected: file.expected(examples, part).map(str::to_string),
+            })
+        })
+        .collect();
+
+    print!("{}", format_examples(&results));
+    results.iter().all(|result| result.correct() != Some(false))
+}
+
+/// The answer of a part, or the error of the part or the parse step that kept it from running.
+fn example_result(records: &[PartRecord], part: u8) -> Result<Option<String>, String> {
+    let record = records
+        .iter()
+        .find(|record| record.part == part)
+        .or_else(|| records.iter().find(|record| record.part == PARSE_PART));
+
+    match record {
+        Some(PartRecord {
+            error: Some(error), ..
+        }) => Err(error.clone()),
+        Some(record) => Ok(record.answer.clone()),
+        None => Ok(None),
+    }
+}
+
+/// Formats example results as a table with a line per file and part.
+fn format_examples(results: &[ExampleResult]) -> String {
+    let rows: Vec<[String; 4]> = results
         .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+        .map(|result| {
+            let answer = match &result.result {
+                Ok(Some(answer)) if !answer.contains('\n') => answer.clone(),
+                Ok(Some(_)) => "▼".into(),
+                Ok(None) => "✖".into(),
+                Err(e) => format!("✖ {e}"),
+            };
+            let verdict = match result.correct() {
+                Some(true) => "✔",
+                Some(false) => "✘",
+                None => "",
+            };
+            [
+                result.file.clone(),
+                format!("Part {}", result.part),
+                answer,
+                result.expected.as_ref().map_or(String::new(), |expected| {
+                    format!("expected {expected} {verdict}")
+                }),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..3)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let padding = " ".repeat(widths[2] - row[2].chars().count());
+            let line = format!(
+                "{:w0$}  {:w1$}  {ANSI_BOLD}{}{ANSI_RESET}{padding}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+            );
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Exit with a non-zero status code if any part did not match its stored answer.
+pub fn exit_on_mismatch(records: &[PartRecord]) {
+    if records.iter().any(|record| record.correct == Some(false)) {
+        process::exit(1);
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Run the parse step of a solution, timing it independently from the parts consuming its output.
+/// If the parse step returns an error, panics or times out, its record is returned as the error instead.
+pub fn run_parse<I, P, E, F>(
+    func: F,
+    input: &Arc<I>,
+    puzzle: PuzzleId,
+    config: &RunConfig,
+) -> Result<Arc<P>, PartRecord>
+where
+    I: ?Sized + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+    E: Into<Box<dyn Error>>,
+    F: Fn(&I) -> Result<P, E> + Copy + Send + 'static,
+{
+    // errors are converted on the solution thread, so the error type does not need to be `Send`.
+    let try_parse = move |input: &I| func(input).map_err(|e| ErrorChain::new(&*e.into()));
+
+    let (parsed, stats) = run_timed(try_parse, input, config, |parsed| {
+        if !config.examples {
+            print!("Parse:");
+        }
+        parsed
+    });
+
+    let parsed = parsed.map_err(ErrorChain::from).and_then(|parsed| parsed);
+    let error = parsed.as_ref().err().cloned();
+
+    if !config.examples {
+        match &error {
+            None => {
+                print!("\r");
+                println!("Parse:{}", format_stats(&stats));
+            }
+            Some(error) => print_result(&Err(error.clone()), "Parse", &format_stats(&stats)),
+        }
+    }
+
+    let record = PartRecord {
+        puzzle,
+        part: PARSE_PART,
+        answer: None,
+        stats,
+        correct: None,
+        error: error.map(|error| error.to_string()),
+    };
+
+    if !config.examples {
+        emit_record(&record);
+    }
+
+    parsed.map(Arc::new).map_err(|_| record)
+}
+
+/// Append a record to the results file if one was requested via [`RESULTS_FILE_ENV`].
+fn emit_record(record: &PartRecord) {
+    let Ok(path) = env::var(RESULTS_FILE_ENV) else {
+        return;
+    };
+
+    let written = serde_json::to_string(record)
+        .map_err(std::io::Error::from)
+        .and_then(|line| {
+            let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+            writeln!(file, "{line}")
+        });
+
+    if let Err(e) = written {
+        eprintln!("Failed to write result record to \"{path}\": {e}");
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether the [`RunConfig`] has a bench configuration:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched according to that [`BenchConfig`].
+///
+/// The result of the first execution is passed through `hook` before benching starts. If the first execution
+/// panics or exceeds the timeout, the failure is returned instead and the function is not benched. A bench execution
+/// that exceeds the timeout fails the run as well.
+fn run_timed<I, T, R, F>(
+    func: F,
+    input: &Arc<I>,
+    config: &RunConfig,
+    hook: impl FnOnce(T) -> R,
+) -> (Result<R, Failure>, Stats)
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+    F: Fn(&I) -> T + Copy + Send + 'static,
+{
+    let (result, base_time) = execute(func, input, config.timeout);
+
+    let result = match result {
+        Ok(result) => hook(result),
+        Err(failure) => return (Err(failure), Stats::from_samples(&[base_time])),
+    };
+
+    let stats = match &config.bench {
+        Some(bench_config) => match bench_with_timeout(func, input, bench_config, config.timeout) {
+            Ok(samples) => Stats::from_samples(&samples),
+            Err(failure) => return (Err(failure), Stats::from_samples(&[base_time])),
+        },
+        None => Stats::from_samples(&[base_time]),
+    };
+
+    (Ok(result), stats)
+}
+
+/// Execute a function once and measure it, capturing panics. With a timeout, the function runs on a separate
+/// thread that is abandoned if it does not finish in time.
+///
+/// Threads cannot be stopped from the outside, so an abandoned thread keeps running, and keeps a CPU busy, until the
+/// function returns or the process exits. Solution binaries exit once all parts have run, while `all --in-process`
+/// carries abandoned threads along until all days are done.
+fn execute<I, T, F>(
+    func: F,
+    input: &Arc<I>,
+    timeout: Option<Duration>,
+) -> (Result<T, Failure>, Duration)
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+    F: Fn(&I) -> T + Send + 'static,
+{
+    let timed = move |input: &I| {
+        let timer = Instant::now();
+        let result = catch_panic(|| func(input));
+        (result, timer.elapsed())
+    };
+
+    let Some(timeout) = timeout else {
+        return timed(input);
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let input = Arc::clone(input);
+
+    spawn_solution_thread(move || {
+        let _ = tx.send(timed(&input));
+    });
+
+    rx.recv_timeout(timeout)
+        .unwrap_or((Err(Failure::TimedOut(timeout)), timeout))
+}
+
+/// Bench a function, see [`bench`]. With a timeout, benching runs on a separate thread and fails as soon as a single
+/// execution takes longer than the timeout. Like in [`execute`], the thread is then abandoned.
+fn bench_with_timeout<I, T, F>(
+    func: F,
+    input: &Arc<I>,
+    config: &BenchConfig,
+    timeout: Option<Duration>,
+) -> Result<Vec<Duration>, Failure>
+where
+    I: ?Sized + Send + Sync + 'static,
+    F: Fn(&I) -> T + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return Ok(bench(func, input, config, &Progress::new()));
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let progress = Arc::new(Progress::new());
+    let (input, config, thread_progress) = (Arc::clone(input), *config, Arc::clone(&progress));
+
+    spawn_solution_thread(move || {
+        let _ = tx.send(catch_panic(|| {
+            bench(func, &input, &config, &thread_progress)
+        }));
+    });
+
+    // wait until the execution in progress reaches the timeout, an execution that started since then gets its own.
+    loop {
+        let deadline = progress.started() + timeout;
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(samples) => return samples,
+            Err(RecvTimeoutError::Timeout) => {
+                if progress.started() + timeout <= Instant::now() {
+                    return Err(Failure::TimedOut(timeout));
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                unreachable!("the bench thread sends its result before exiting")
+            }
+        }
+    }
+}
+
+/// Spawn a thread for a solution function, with the stack size of the main thread.
+fn spawn_solution_thread(func: impl FnOnce() + Send + 'static) {
+    let spawned = thread::Builder::new()
+        .stack_size(THREAD_STACK_SIZE)
+        .spawn(func);
+
+    if let Err(e) = spawned {
+        eprintln!("Failed to spawn solution thread: {e}");
+        process::exit(1);
+    }
+}
+
+thread_local! {
+    /// Whether the current thread is running a solution function, see [`catch_panic`].
+    static IS_SOLUTION_THREAD: Cell<bool> = const { Cell::new(false) };
+    /// Location of the last panic in a solution function on the current thread.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Call a function, converting a panic into a [`Failure`]. Panics inside the function are reported through the
+/// returned failure rather than the panic hook, unless `RUST_BACKTRACE` is set.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, Failure> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_SOLUTION_THREAD.with(Cell::get) {
+                default_hook(info);
+                return;
+            }
+
+            PANIC_LOCATION.with(|location| {
+                *location.borrow_mut() = info.location().map(ToString::to_string);
+            });
+
+            if env::var_os("RUST_BACKTRACE").is_some() {
+                default_hook(info);
+            }
+        }));
+    });
+
+    IS_SOLUTION_THREAD.with(|is_solution| is_solution.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_SOLUTION_THREAD.with(|is_solution| is_solution.set(false));
+
+    result.map_err(|payload| Failure::Panicked {
+        message: payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".into()),
+        location: PANIC_LOCATION.with(RefCell::take),
+    })
+}
+
+/// Start time of the execution in progress on a bench thread, watched by [`bench_with_timeout`].
+struct Progress {
+    origin: Instant,
+    /// Nanoseconds between `origin` and the start of the execution in progress.
+    started: AtomicU64,
+}
+
+impl Progress {
+    fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            started: AtomicU64::new(0),
+        }
+    }
+
+    fn start(&self) {
+        let nanos = u64::try_from(self.origin.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.started.store(nanos, Ordering::Relaxed);
+    }
+
+    fn started(&self) -> Instant {
+        self.origin + Duration::from_nanos(self.started.load(Ordering::Relaxed))
+    }
+}
+
+/// Bench a function: warm up for the configured duration, then collect as many samples as fit in the time budget.
+/// The start of every execution is recorded in `progress`.
+fn bench<I: ?Sized, T>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    config: &BenchConfig,
+    progress: &Progress,
+) -> Vec<Duration> {
+    let mut stdout = stdout();
+
+    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    let _ = stdout.flush();
+
+    let warmup_timer = Instant::now();
+    let mut warmup_iterations: u32 = 0;
+
+    while warmup_iterations == 0 || warmup_timer.elapsed() < config.warmup {
+        progress.start();
+        func(input);
+        warmup_iterations += 1;
+    }
+
+    let estimate = warmup_timer.elapsed() / warmup_iterations;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let bench_iterations = (config.budget.as_nanos() / estimate.as_nanos().max(10)) as u64;
+    let bench_iterations = bench_iterations.clamp(config.min_samples, config.max_samples);
+
+    (0..bench_iterations)
+        .map(|_| {
+            progress.start();
+            let timer = Instant::now();
+            func(input);
+            timer.elapsed()
+        })
+        .collect()
+}
+
+fn format_stats(stats: &Stats) -> String {
+    let median = stats.median();
+
+    if stats.samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        let stddev = stats.stddev();
+        format!(" ({median:.1?} ± {stddev:.1?} @ {} samples)", stats.samples)
+    }
+}
+
+fn format_verdict(correct: Option<bool>, expected: Option<&str>) -> String {
+    match (correct, expected) {
+        (Some(true), _) => " ✔".into(),
+        (Some(false), Some(expected)) => format!(" ✘ (expected {expected})"),
+        _ => String::new(),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Result<Option<String>, ErrorChain>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -116,7 +1066,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,44 +1074,372 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                let (message, sources) = e.messages().split_first().unwrap();
+                print!("\r");
+                println!("{part}: ✖ {ANSI_BOLD}{message}{ANSI_RESET}{duration_str}");
+                for source in sources {
+                    println!("  caused by: {source}");
+                }
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit the answer to one part of the solution. Answers that previous submissions show to be wrong are
+/// not submitted. Prints and returns the verdict, which is added to the stored submissions.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+) -> Option<Result<Verdict, aoc_client::Error>> {
+    let answer = result.to_string();
+
+    let mut submissions = submissions::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read previous submissions: {e}");
+        process::exit(1);
+    });
 
-    if !args.contains(&"--submit".into()) {
+    if let Err(refusal) = submissions.check(part, &answer, submissions::now()) {
+        eprintln!(
+            "Not submitting {answer}: {refusal} See \"{}\".",
+            submissions::get_path(puzzle).display()
+        );
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => return Some(Err(e)),
+    };
+
+    println!("Submitting result...");
+    let verdict = client.submit(puzzle, part, &answer);
+
+    if let Ok(verdict) = &verdict {
+        println!("{verdict}");
+
+        submissions.record(part, &answer, verdict, submissions::now());
+        if let Err(e) = submissions::write(puzzle, &submissions) {
+            eprintln!("Failed to store submission: {e}");
+        }
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    Some(verdict)
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        thread,
+        time::{Duration, Instant},
     };
 
-    if part_submit != part {
-        return None;
+    use super::{
+        bench_with_timeout, catch_panic, execute, format_examples, parse_duration, run_parse,
+        BenchConfig, ExampleResult, Failure, InputSource, PartOutput, RunConfig, Stats, PARSE_PART,
+    };
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use crate::{day, year, PuzzleId};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&n| Duration::from_nanos(n)).collect()
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn stats_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.median_nanos, 42);
+        assert_eq!(stats.mean_nanos, 42);
+        assert_eq!(stats.p95_nanos, 42);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn stats_summary() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min_nanos, 1);
+        assert_eq!(stats.max_nanos, 5);
+        assert_eq!(stats.median_nanos, 3);
+        assert_eq!(stats.mean_nanos, 3);
+        assert_eq!(stats.p95_nanos, 5);
+        assert!((stats.stddev_nanos - 2_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stats_excludes_outliers_from_mean() {
+        let stats = Stats::from_samples(&nanos(&[10, 10, 11, 10, 12, 11, 10, 1000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max_nanos, 1000);
+        assert_eq!(stats.mean_nanos, 11);
+        assert_eq!(stats.median_nanos, 10);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ns"), Ok(Duration::from_nanos(250)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("ms").is_err());
+    }
+
+    #[test]
+    fn bench_config_roundtrip() {
+        let config = BenchConfig {
+            warmup: Duration::from_millis(5),
+            budget: Duration::from_secs(3),
+            min_samples: 2,
+            max_samples: 50,
+        };
+        assert_eq!(BenchConfig::from_args(&config.to_args()), Ok(config));
+        assert_eq!(BenchConfig::from_args(&[]), Ok(BenchConfig::default()));
+    }
+
+    #[test]
+    fn validates_bench_config() {
+        let config = BenchConfig::default();
+        assert_eq!(config.validate(), Ok(()));
+
+        let invalid = [
+            BenchConfig {
+                budget: Duration::ZERO,
+                ..config
+            },
+            BenchConfig {
+                max_samples: 0,
+                ..config
+            },
+            BenchConfig {
+                min_samples: 0,
+                ..config
+            },
+            BenchConfig {
+                min_samples: 20,
+                max_samples: 10,
+                ..config
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+            assert!(BenchConfig::from_args(&config.to_args()).is_err());
+        }
+    }
+
+    #[test]
+    fn run_config_from_args() {
+        let args = |args: &[&str]| args.iter().map(|&arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            RunConfig::from_args(&args(&["2023-05"])),
+            Ok(RunConfig::default())
+        );
+        assert_eq!(
+            RunConfig::from_args(&args(&["2023-05", "--time", "--timeout", "2s"])),
+            Ok(RunConfig {
+                timeout: Some(Duration::from_secs(2)),
+                bench: Some(BenchConfig::default()),
+                ..RunConfig::default()
+            })
+        );
+        assert!(RunConfig::from_args(&args(&["2023-05", "--timeout", "2"])).is_err());
+        assert_eq!(
+            RunConfig::from_args(&args(&["2023-05", "--submit", "2", "--input", "-"])),
+            Ok(RunConfig {
+                input: InputSource::Stdin,
+                submit: Some(2),
+                ..RunConfig::default()
+            })
+        );
+        assert!(RunConfig::from_args(&args(&["2023-05", "--submit"])).is_err());
+        assert!(RunConfig::from_args(&args(&["2023-05", "--submit", "x"])).is_err());
+    }
+
+    #[test]
+    fn input_sources() {
+        let args = |args: &[&str]| args.iter().map(|&arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            InputSource::from_args(&args(&["2023-05"])),
+            InputSource::Puzzle
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["2023-05", "--input", "big.txt", "--time"])),
+            InputSource::File("big.txt".into())
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["2023-05", "--input", "-"])),
+            InputSource::Stdin
+        );
+        assert!(!InputSource::Puzzle.is_custom());
+        assert!(InputSource::Stdin.is_custom());
+    }
+
+    #[test]
+    fn formats_examples() {
+        let result = |file: &str, part, result, expected: Option<&str>| ExampleResult {
+            file: file.into(),
+            part,
+            result,
+            expected: expected.map(str::to_string),
+        };
+        let results = [
+            result("01-1.txt", 1, Ok(Some("142".into())), Some("142")),
+            result("01-2.txt", 2, Ok(Some("2".into())), Some("281")),
+            result("01-3.txt", 1, Err("panicked: oops".into()), None),
+        ];
+
+        assert_eq!(
+            results.clone().map(|result| result.correct()),
+            [Some(true), Some(false), None]
+        );
+        assert_eq!(
+            format_examples(&results),
+            format!(
+                "01-1.txt  Part 1  {ANSI_BOLD}142{ANSI_RESET}               expected 142 ✔\n\
+                 01-2.txt  Part 2  {ANSI_BOLD}2{ANSI_RESET}                 expected 281 ✘\n\
+                 01-3.txt  Part 1  {ANSI_BOLD}✖ panicked: oops{ANSI_RESET}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn part_outputs() {
+        assert_eq!(Some(42).into_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.into_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(7).into_answer(), Ok(Some("7".into())));
+
+        let error = Err::<u32, _>("no seeds").into_answer().unwrap_err();
+        assert_eq!(error.messages(), ["no seeds"]);
+    }
+
+    #[test]
+    fn error_chain_includes_sources() {
+        #[derive(Debug)]
+        struct Outer(std::num::ParseIntError);
+
+        impl std::fmt::Display for Outer {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid mapping")
+            }
+        }
+
+        impl std::error::Error for Outer {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let source = "x".parse::<u64>().unwrap_err();
+        let error = Err::<u32, _>(Outer(source)).into_answer().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid mapping: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn parse_errors_fail_the_parse_step() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        let config = RunConfig::default();
+
+        let input: Arc<str> = "42".into();
+        let parsed = run_parse(str::parse::<u32>, &input, puzzle, &config);
+        assert_eq!(parsed.map(|parsed| *parsed), Ok(42));
+
+        let input: Arc<str> = "9x".into();
+        let record = run_parse(str::parse::<u32>, &input, puzzle, &config).unwrap_err();
+        assert_eq!(record.part, PARSE_PART);
+        assert_eq!(record.answer, None);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn captures_panics() {
+        let failure = catch_panic(|| panic!("boom")).unwrap_err();
+        assert!(matches!(failure, Failure::Panicked { message, .. } if message == "boom"));
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn times_out() {
+        let input: Arc<str> = "".into();
+        let timeout = Duration::from_millis(10);
+
+        let (result, _) = execute(
+            |_: &str| thread::sleep(Duration::from_secs(1)),
+            &input,
+            Some(timeout),
+        );
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+
+        let (result, _) = execute(str::len, &input, Some(Duration::from_secs(1)));
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn bench_times_out() {
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+
+        let input: Arc<str> = "".into();
+        let timeout = Duration::from_millis(10);
+        let config = BenchConfig {
+            warmup: Duration::ZERO,
+            budget: Duration::from_millis(1),
+            min_samples: 5,
+            max_samples: 5,
+        };
+
+        let hangs_later = |_: &str| {
+            if CALLS.fetch_add(1, Ordering::Relaxed) > 2 {
+                thread::sleep(Duration::from_secs(1));
+            }
+        };
+        let result = bench_with_timeout(hangs_later, &input, &config, Some(timeout));
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+
+        let samples = bench_with_timeout(str::len, &input, &config, Some(timeout)).unwrap();
+        assert_eq!(samples.len(), 5);
+    }
+
+    #[test]
+    fn bench_timeout_applies_to_each_execution() {
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+
+        let input: Arc<str> = "".into();
+        let timeout = Duration::from_millis(250);
+        let config = BenchConfig {
+            warmup: Duration::ZERO,
+            budget: Duration::from_millis(1),
+            min_samples: 5,
+            max_samples: 5,
+        };
+
+        // four executions within the timeout, the fifth starts after 600ms and times out 250ms later.
+        let hangs_later = |_: &str| {
+            let duration = if CALLS.fetch_add(1, Ordering::Relaxed) < 4 {
+                Duration::from_millis(150)
+            } else {
+                Duration::from_secs(5)
+            };
+            thread::sleep(duration);
+        };
+
+        let timer = Instant::now();
+        let result = bench_with_timeout(hangs_later, &input, &config, Some(timeout));
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+        assert!(timer.elapsed() < Duration::from_millis(925));
+    }
 }
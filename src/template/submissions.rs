@@ -0,0 +1,349 @@
+/// Log of submitted answers and their verdicts, stored as one TOML file per day in `data/<year>/submissions`.
+/// Used to refuse submissions that are known to be wrong before they reach the website.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{aoc_client::Verdict, get_data_path, project_root};
+use crate::PuzzleId;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submissions file: {e}"),
+            Error::IO(e) => write!(f, "could not access submissions file: {e}"),
+        }
+    }
+}
+
+/// Stored form of a [`Verdict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    WrongLevel,
+    Unknown,
+}
+
+impl From<&Verdict> for Outcome {
+    fn from(verdict: &Verdict) -> Self {
+        match verdict {
+            Verdict::Correct => Outcome::Correct,
+            Verdict::Wrong { .. } => Outcome::Wrong,
+            Verdict::TooHigh { .. } => Outcome::TooHigh,
+            Verdict::TooLow { .. } => Outcome::TooLow,
+            Verdict::RateLimited { .. } => Outcome::RateLimited,
+            Verdict::WrongLevel => Outcome::WrongLevel,
+            Verdict::Unknown(_) => Outcome::Unknown,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::RateLimited => "rate limited",
+            Outcome::WrongLevel => "for the wrong level",
+            Outcome::Unknown => "unknown",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Outcome {
+    /// Whether the outcome proves the answer to be wrong.
+    #[must_use]
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reasons to not submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong {
+        outcome: Outcome,
+    },
+    /// The answer is at least as high as an answer that was too high.
+    AboveBound {
+        bound: i128,
+    },
+    /// The answer is at most as low as an answer that was too low.
+    BelowBound {
+        bound: i128,
+    },
+    Cooldown {
+        remaining: Duration,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with {answer}.")
+            }
+            Refusal::KnownWrong { outcome } => {
+                write!(f, "this answer was already submitted and is {outcome}.")
+            }
+            Refusal::AboveBound { bound } => write!(f, "answers >= {bound} are too high."),
+            Refusal::BelowBound { bound } => write!(f, "answers <= {bound} are too low."),
+            Refusal::Cooldown { remaining } => write!(
+                f,
+                "the website asked to wait before submitting again, {}s left.",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+/// The submissions of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submissions {
+    /// Seconds since the unix epoch until which no answer should be submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown_until: Option<u64>,
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Checks whether submitting an answer for a part can succeed, given the previous submissions.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let remaining = self
+            .cooldown_until
+            .map_or(0, |until| until.saturating_sub(now));
+
+        if remaining > 0 {
+            return Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(remaining),
+            });
+        }
+
+        let submissions = self.for_part(part);
+
+        if let Some(solved) = submissions.clone().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if let Some(known) = submissions
+            .clone()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Err(Refusal::KnownWrong {
+                outcome: known.outcome,
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome: Outcome| {
+            submissions
+                .clone()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Outcome::TooHigh).min().filter(|&b| value >= b) {
+            return Err(Refusal::AboveBound { bound });
+        }
+
+        if let Some(bound) = bound(Outcome::TooLow).max().filter(|&b| value <= b) {
+            return Err(Refusal::BelowBound { bound });
+        }
+
+        Ok(())
+    }
+
+    /// Adds a submission and the cooldown the website asked for.
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict, now: u64) {
+        self.submissions.push(Submission {
+            part,
+            answer: answer.into(),
+            outcome: verdict.into(),
+            timestamp: now,
+        });
+
+        let wait = match verdict {
+            Verdict::Wrong { wait } | Verdict::TooHigh { wait } | Verdict::TooLow { wait } => *wait,
+            Verdict::RateLimited { wait } => Some(*wait),
+            _ => None,
+        };
+
+        self.cooldown_until = wait.map(|wait| now + wait.as_secs());
+    }
+
+    fn for_part(&self, part: u8) -> impl Iterator<Item = &Submission> + Clone {
+        self.submissions.iter().filter(move |s| s.part == part)
+    }
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    project_root().join(get_data_path(
+        "submissions",
+        puzzle.year,
+        &format!("{}.toml", puzzle.day),
+    ))
+}
+
+/// Reads the submissions of a day. A missing file yields empty [`Submissions`].
+pub fn read(puzzle: PuzzleId) -> Result<Submissions, Error> {
+    match fs::read_to_string(get_path(puzzle)) {
+        Ok(contents) => toml::from_str(&contents).map_err(|e| Error::Parser(e.to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn write(puzzle: PuzzleId, submissions: &Submissions) -> Result<(), Error> {
+    let contents = toml::to_string(submissions).map_err(|e| Error::Parser(e.to_string()))?;
+    let path = get_path(puzzle);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Outcome, Refusal, Submissions};
+    use crate::template::aoc_client::Verdict;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(1, "100", &Verdict::TooHigh { wait: None }, NOW);
+        submissions.record(1, "10", &Verdict::TooLow { wait: None }, NOW);
+        submissions.record(1, "abc", &Verdict::Wrong { wait: None }, NOW);
+        submissions
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = submissions();
+
+        assert_eq!(
+            submissions.check(1, "abc", NOW),
+            Err(Refusal::KnownWrong {
+                outcome: Outcome::Wrong
+            })
+        );
+        assert_eq!(
+            submissions.check(1, "150", NOW),
+            Err(Refusal::AboveBound { bound: 100 })
+        );
+        assert_eq!(
+            submissions.check(1, "10", NOW),
+            Err(Refusal::KnownWrong {
+                outcome: Outcome::TooLow
+            })
+        );
+        assert_eq!(
+            submissions.check(1, "-5", NOW),
+            Err(Refusal::BelowBound { bound: 10 })
+        );
+        assert_eq!(submissions.check(1, "50", NOW), Ok(()));
+        assert_eq!(submissions.check(2, "150", NOW), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = submissions();
+        submissions.record(1, "42", &Verdict::Correct, NOW);
+
+        assert_eq!(
+            submissions.check(1, "50", NOW),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn honors_cooldown() {
+        let mut submissions = Submissions::default();
+        submissions.record(
+            1,
+            "1",
+            &Verdict::Wrong {
+                wait: Some(Duration::from_secs(60)),
+            },
+            NOW,
+        );
+
+        assert_eq!(
+            submissions.check(2, "2", NOW + 15),
+            Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(45)
+            })
+        );
+        assert_eq!(submissions.check(2, "2", NOW + 60), Ok(()));
+    }
+
+    #[test]
+    fn serializes_to_toml() {
+        let submissions = submissions();
+        let serialized = toml::to_string(&submissions).unwrap();
+
+        assert!(serialized.contains("[[submission]]"));
+        assert!(serialized.contains("outcome = \"too_high\""));
+        assert_eq!(
+            toml::from_str::<Submissions>(&serialized).unwrap(),
+            submissions
+        );
+    }
+}
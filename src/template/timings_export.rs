@@ -0,0 +1,212 @@
+/// Exports benchmark timings in formats other tools can consume.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::{
+    readme_benchmarks::{construct_table, Timings},
+    runner::{Stats, PARSE_PART},
+};
+use crate::Year;
+
+#[derive(Debug)]
+pub enum Error {
+    Serializer(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Serializer(e) => write!(f, "could not serialize timings: {e}"),
+            Error::IO(e) => write!(f, "could not write timings: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    #[default]
+    Markdown,
+}
+
+#[derive(Debug)]
+pub struct FormatFromStrError(String);
+
+impl std::error::Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid format \"{}\", expecting one of json, csv, markdown",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(FormatFromStrError(s.into())),
+        }
+    }
+}
+
+/// Where and how `all` exports timings instead of updating the README.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Export {
+    pub format: Format,
+    /// Output file, timings are printed to stdout if [`None`].
+    pub output: Option<PathBuf>,
+}
+
+const CSV_HEADER: &str = "year,day,part,median_nanos,mean_nanos,min_nanos,max_nanos,p95_nanos,stddev_nanos,samples,outliers";
+
+/// Renders the timings of a year in the given format.
+pub fn render(
+    format: Format,
+    year: Year,
+    timings: &[Timings],
+    total_millis: f64,
+) -> Result<String, Error> {
+    match format {
+        Format::Json => {
+            serde_json::to_string_pretty(timings).map_err(|e| Error::Serializer(e.to_string()))
+        }
+        Format::Csv => Ok(render_csv(timings)),
+        Format::Markdown => Ok(construct_table("#", year, timings, total_millis)),
+    }
+}
+
+/// One row per measured part, with [`PARSE_PART`] as part number of the parse step.
+fn render_csv(timings: &[Timings]) -> String {
+    let mut lines: Vec<String> = vec![CSV_HEADER.into()];
+
+    for timing in timings {
+        let parts = [
+            (PARSE_PART, timing.parse),
+            (1, timing.part_1),
+            (2, timing.part_2),
+        ];
+
+        for (part, stats) in parts {
+            let Some(Stats {
+                mean_nanos,
+                median_nanos,
+                min_nanos,
+                max_nanos,
+                p95_nanos,
+                stddev_nanos,
+                samples,
+                outliers,
+            }) = stats
+            else {
+                continue;
+            };
+
+            lines.push(format!(
+                "{},{},{part},{median_nanos},{mean_nanos},{min_nanos},{max_nanos},{p95_nanos},{stddev_nanos:.1},{samples},{outliers}",
+                timing.puzzle.year,
+                timing.puzzle.day.into_inner(),
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Writes the rendered timings to a file, or to stdout if no path is given.
+pub fn write(
+    format: Format,
+    output: Option<&Path>,
+    year: Year,
+    timings: &[Timings],
+    total_millis: f64,
+) -> Result<(), Error> {
+    let rendered = render(format, year, timings, total_millis)?;
+
+    match output {
+        Some(path) => {
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, rendered)?;
+        }
+        None => println!("\n{rendered}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Format};
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::Stats;
+    use crate::{day, year, PuzzleId};
+
+    fn get_mock_timings() -> Vec<Timings> {
+        let stats = |median_nanos| {
+            Some(Stats {
+                median_nanos,
+                samples: 1,
+                ..Stats::default()
+            })
+        };
+
+        vec![Timings {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            parse: stats(5),
+            part_1: stats(10),
+            part_2: None,
+            total_nanos: 15.0,
+        }]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(Format::Csv, year!(2023), &get_mock_timings(), 0.0).unwrap();
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "year,day,part,median_nanos,mean_nanos,min_nanos,max_nanos,p95_nanos,stddev_nanos,samples,outliers",
+                "2023,5,0,5,0,0,0,0,0.0,1,0",
+                "2023,5,1,10,0,0,0,0,0.0,1,0",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(Format::Json, year!(2023), &get_mock_timings(), 0.0).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["year"], 2023);
+        assert_eq!(value[0]["day"], 5);
+        assert_eq!(value[0]["part_1"]["median_nanos"], 10);
+        assert!(value[0]["part_2"].is_null());
+    }
+}
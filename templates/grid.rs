@@ -0,0 +1,33 @@
+//! Day {{day}}: {{title}}
+use advent_of_code::grid::Grid;
+
+advent_of_code::solution!({{year}}, {{day}}, parse);
+
+fn parse(input: &str) -> Grid<char> {
+    input.parse().unwrap()
+}
+
+pub fn part_one(_grid: &Grid<char>) -> Option<{{return_type}}> {
+    None
+}
+
+pub fn part_two(_grid: &Grid<char>) -> Option<{{return_type}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&{{part_one_example}}));
+        assert_eq!(result, {{part_one_answer}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&{{part_two_example}}));
+        assert_eq!(result, {{part_two_answer}});
+    }
+}
@@ -0,0 +1,35 @@
+//! Day {{day}}: {{title}}
+advent_of_code::solution!({{year}}, {{day}}, Input::parse);
+
+pub struct Input {}
+
+impl Input {
+    fn parse(_input: &str) -> Self {
+        Self {}
+    }
+}
+
+pub fn part_one(_input: &Input) -> Option<{{return_type}}> {
+    None
+}
+
+pub fn part_two(_input: &Input) -> Option<{{return_type}}> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&Input::parse(&{{part_one_example}}));
+        assert_eq!(result, {{part_one_answer}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&Input::parse(&{{part_two_example}}));
+        assert_eq!(result, {{part_two_answer}});
+    }
+}